@@ -0,0 +1,216 @@
+use std::{
+    fmt,
+    ops::{Add, BitXor, Sub},
+};
+
+use crate::{bounded::Bounded, modint2::*};
+
+pub trait Monoid {
+    fn id() -> Self;
+    fn op(&self, other: &Self) -> Self;
+}
+
+/// `op` is commutative.
+pub trait CommutativeMonoid: Monoid {}
+
+pub trait Group: Monoid {
+    fn inv(&self) -> Self;
+
+    /// `self.op(&other.inv())`, without computing the inverse on its own
+    /// where that may overflow.
+    fn op_inv(&self, other: &Self) -> Self
+    where
+        Self: Sized,
+    {
+        self.op(&other.inv())
+    }
+}
+
+/// Monoid acting on `T`. `f.op(g).act(x) == f.act(&g.act(x))` and `act`
+/// distributes over `T::op`.
+pub trait Action<T>: Monoid {
+    fn act(&self, x: &T) -> T;
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub struct Sum<T>(pub T);
+
+impl<T: Clone + Default + Add<Output = T>> Monoid for Sum<T> {
+    fn id() -> Self {
+        Sum(T::default())
+    }
+    fn op(&self, other: &Self) -> Self {
+        Sum(self.0.clone() + other.0.clone())
+    }
+}
+
+impl<T: Clone + Default + Add<Output = T>> CommutativeMonoid for Sum<T> {}
+
+impl<T: Clone + Default + Add<Output = T> + Sub<Output = T>> Group for Sum<T> {
+    fn inv(&self) -> Self {
+        Sum(T::default() - self.0.clone())
+    }
+    fn op_inv(&self, other: &Self) -> Self {
+        Sum(self.0.clone() - other.0.clone())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Min<T>(pub T);
+
+impl<T: Clone + PartialOrd + Bounded> Monoid for Min<T> {
+    fn id() -> Self {
+        Min(T::max_value())
+    }
+    fn op(&self, other: &Self) -> Self {
+        if other.0 < self.0 {
+            other.clone()
+        } else {
+            self.clone()
+        }
+    }
+}
+
+impl<T: Clone + PartialOrd + Bounded> CommutativeMonoid for Min<T> {}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Max<T>(pub T);
+
+impl<T: Clone + PartialOrd + Bounded> Monoid for Max<T> {
+    fn id() -> Self {
+        Max(T::min_value())
+    }
+    fn op(&self, other: &Self) -> Self {
+        if other.0 > self.0 {
+            other.clone()
+        } else {
+            self.clone()
+        }
+    }
+}
+
+impl<T: Clone + PartialOrd + Bounded> CommutativeMonoid for Max<T> {}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub struct Xor<T>(pub T);
+
+impl<T: Clone + Default + BitXor<Output = T>> Monoid for Xor<T> {
+    fn id() -> Self {
+        Xor(T::default())
+    }
+    fn op(&self, other: &Self) -> Self {
+        Xor(self.0.clone() ^ other.0.clone())
+    }
+}
+
+impl<T: Clone + Default + BitXor<Output = T>> CommutativeMonoid for Xor<T> {}
+
+impl<T: Clone + Default + BitXor<Output = T>> Group for Xor<T> {
+    fn inv(&self) -> Self {
+        self.clone()
+    }
+}
+
+/// `x -> ax + b`. `f.op(g)` is the composition `f(g(x))`.
+pub struct Affine<M> {
+    pub a: ModInt<M>,
+    pub b: ModInt<M>,
+}
+
+impl<M> Clone for Affine<M> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M> Copy for Affine<M> {}
+
+impl<M> PartialEq for Affine<M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.a == other.a && self.b == other.b
+    }
+}
+
+impl<M> Eq for Affine<M> {}
+
+impl<M> fmt::Debug for Affine<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Affine")
+            .field("a", &self.a)
+            .field("b", &self.b)
+            .finish()
+    }
+}
+
+impl<M: Modulo> Affine<M> {
+    pub fn new(a: ModInt<M>, b: ModInt<M>) -> Self {
+        Self { a, b }
+    }
+
+    pub fn apply(&self, x: ModInt<M>) -> ModInt<M> {
+        self.a * x + self.b
+    }
+}
+
+impl<M: Modulo> Monoid for Affine<M> {
+    fn id() -> Self {
+        Self::new(ModInt::ONE, ModInt::ZERO)
+    }
+    fn op(&self, other: &Self) -> Self {
+        Self::new(self.a * other.a, self.a * other.b + self.b)
+    }
+}
+
+/// Acts on `(sum, length)` pairs.
+impl<M: Modulo> Action<(Sum<ModInt<M>>, Sum<ModInt<M>>)> for Affine<M> {
+    fn act(&self, x: &(Sum<ModInt<M>>, Sum<ModInt<M>>)) -> (Sum<ModInt<M>>, Sum<ModInt<M>>) {
+        (Sum(self.a * x.0 .0 + self.b * x.1 .0), x.1)
+    }
+}
+
+/// Trivial monoid, acting as the identity.
+impl Monoid for () {
+    fn id() -> Self {}
+    fn op(&self, _other: &Self) -> Self {}
+}
+
+impl CommutativeMonoid for () {}
+
+impl Group for () {
+    fn inv(&self) -> Self {}
+}
+
+impl<T: Clone> Action<T> for () {
+    fn act(&self, x: &T) -> T {
+        x.clone()
+    }
+}
+
+macro_rules! tuple {
+    ($($T:ident $i:tt),*) => {
+        impl<$($T: Monoid),*> Monoid for ($($T,)*) {
+            fn id() -> Self {
+                ($($T::id(),)*)
+            }
+            fn op(&self, other: &Self) -> Self {
+                ($(self.$i.op(&other.$i),)*)
+            }
+        }
+
+        impl<$($T: CommutativeMonoid),*> CommutativeMonoid for ($($T,)*) {}
+
+        impl<$($T: Group),*> Group for ($($T,)*) {
+            fn inv(&self) -> Self {
+                ($(self.$i.inv(),)*)
+            }
+            fn op_inv(&self, other: &Self) -> Self {
+                ($(self.$i.op_inv(&other.$i),)*)
+            }
+        }
+    };
+}
+
+tuple!(A 0);
+tuple!(A 0, B 1);
+tuple!(A 0, B 1, C 2);
+tuple!(A 0, B 1, C 2, D 3);
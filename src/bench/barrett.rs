@@ -4,6 +4,7 @@ use crate::random::*;
 use test::Bencher;
 
 use crate::experimental::BarrettReduction;
+use crate::montgomery::{Montgomery32, Montgomery64, MontgomeryReduction};
 
 #[bench]
 fn barrett_small(b: &mut Bencher) {
@@ -141,3 +142,97 @@ fn rem_u64(b: &mut Bencher, f: impl Fn(&[u32], &[u64]) -> u32) {
     let xs: Vec<u64> = (0..n).map(|_| rng.gen()).collect();
     b.iter(|| f(&ms, &xs));
 }
+
+#[bench]
+fn native_mul_u32(b: &mut Bencher) {
+    mul_odd_u32(b, |ms, xs| {
+        let mut sum = 0u32;
+        for m in ms {
+            let mut acc = 1u64;
+            for x in xs {
+                acc = acc * *x as u64 % *m as u64;
+            }
+            sum = sum.wrapping_add(acc as u32);
+        }
+        sum
+    })
+}
+
+#[bench]
+fn barrett_mul_u32(b: &mut Bencher) {
+    mul_odd_u32(b, |ms, xs| {
+        let mut sum = 0u32;
+        for m in ms {
+            let br = BarrettReduction::new(*m);
+            let mut acc = 1u32;
+            for x in xs {
+                acc = br.rem(acc as u64 * *x as u64);
+            }
+            sum = sum.wrapping_add(acc);
+        }
+        sum
+    })
+}
+
+#[bench]
+fn montgomery_mul_u32(b: &mut Bencher) {
+    mul_odd_u32(b, |ms, xs| {
+        let mut sum = 0u32;
+        for m in ms {
+            let mr = Montgomery32::new(*m);
+            let mut acc = mr.encode(1);
+            for x in xs {
+                acc = mr.mul(acc, *x);
+            }
+            sum = sum.wrapping_add(mr.decode(acc) as u32);
+        }
+        sum
+    })
+}
+
+#[bench]
+fn native_mul_u64(b: &mut Bencher) {
+    mul_odd_u64(b, |ms, xs| {
+        let mut sum = 0u64;
+        for m in ms {
+            let mut acc = 1u64;
+            for x in xs {
+                acc = (acc as u128 * *x as u128 % *m as u128) as u64;
+            }
+            sum = sum.wrapping_add(acc);
+        }
+        sum
+    })
+}
+
+#[bench]
+fn montgomery_mul_u64(b: &mut Bencher) {
+    mul_odd_u64(b, |ms, xs| {
+        let mut sum = 0u64;
+        for m in ms {
+            let mr = Montgomery64::new(*m);
+            let mut acc = mr.encode(1);
+            for x in xs {
+                acc = mr.mul(acc, *x);
+            }
+            sum = sum.wrapping_add(mr.decode(acc));
+        }
+        sum
+    })
+}
+
+fn mul_odd_u32(b: &mut Bencher, f: impl Fn(&[u32], &[u32]) -> u32) {
+    let mut rng = Xoshiro::seed_from_u64(1);
+    let n = 1000;
+    let ms: Vec<u32> = (0..n).map(|_| rng.range(1, 1 << 30) * 2 + 1).collect();
+    let xs: Vec<u32> = (0..n).map(|_| rng.range(0, 1 << 31)).collect();
+    b.iter(|| f(&ms, &xs));
+}
+
+fn mul_odd_u64(b: &mut Bencher, f: impl Fn(&[u64], &[u64]) -> u64) {
+    let mut rng = Xoshiro::seed_from_u64(1);
+    let n = 1000;
+    let ms: Vec<u64> = (0..n).map(|_| rng.range(1, 1 << 62) * 2 + 1).collect();
+    let xs: Vec<u64> = (0..n).map(|_| rng.range(0, 1 << 63)).collect();
+    b.iter(|| f(&ms, &xs));
+}
@@ -1,27 +1,31 @@
 use std::collections::VecDeque;
 
+use crate::graph2::IndexedGraph;
+
 const NIL: Idx = !0;
 const INF: Idx = !0;
 
 type Idx = u32;
 
-pub fn bipartite_matching(g: &[Vec<usize>], n_end: usize) -> Vec<(usize, usize)> {
+/// Maximum matching by Hopcroft-Karp, where `g` has an edge from each left
+/// node to the right nodes `0..n_end` it can be matched with.
+pub fn bipartite_matching<G: IndexedGraph + ?Sized>(g: &G, n_end: usize) -> Vec<(usize, usize)> {
     let mut f = BipertiteMatching::new(g, n_end);
     f.run();
     f.matches()
 }
 
-struct BipertiteMatching<'a> {
-    g: &'a [Vec<usize>],
+struct BipertiteMatching<'a, G: ?Sized> {
+    g: &'a G,
     rtol: Vec<Idx>,
     dist: Vec<Idx>,
     que: VecDeque<Idx>,
 }
 
-impl<'a> BipertiteMatching<'a> {
+impl<'a, G: IndexedGraph + ?Sized> BipertiteMatching<'a, G> {
     #[inline]
-    fn new(g: &'a [Vec<usize>], n_end: usize) -> Self {
-        let n = g.len();
+    fn new(g: &'a G, n_end: usize) -> Self {
+        let n = g.num_nodes();
         Self {
             g,
             rtol: vec![NIL; n_end],
@@ -36,7 +40,7 @@ impl<'a> BipertiteMatching<'a> {
             if !self.bfs() {
                 break;
             }
-            for l in 0..self.g.len() {
+            for l in 0..self.g.num_nodes() {
                 if self.dist[l] == 0 && self.dfs(l as Idx) {
                     self.dist[l] = INF;
                 }
@@ -58,7 +62,7 @@ impl<'a> BipertiteMatching<'a> {
     #[inline]
     fn bfs(&mut self) -> bool {
         self.que.clear();
-        for l in 0..self.g.len() {
+        for l in 0..self.g.num_nodes() {
             if self.dist[l] == 0 {
                 self.que.push_back(l as Idx);
             } else {
@@ -67,7 +71,7 @@ impl<'a> BipertiteMatching<'a> {
         }
         let mut reached = false;
         while let Some(l) = self.que.pop_front() {
-            for &r in &self.g[l as usize] {
+            for r in self.g.neighbors(l as usize) {
                 let lb = self.rtol[r];
                 reached |= lb == NIL;
                 if lb != NIL && self.dist[lb as usize] == INF {
@@ -83,7 +87,8 @@ impl<'a> BipertiteMatching<'a> {
     fn dfs(&mut self, l: Idx) -> bool {
         let dist_l = self.dist[l as usize];
         self.dist[l as usize] = if dist_l != 0 { INF } else { 0 };
-        for &r in &self.g[l as usize] {
+        let g = self.g;
+        for r in g.neighbors(l as usize) {
             let lb = self.rtol[r];
             if lb == NIL || (dist_l + 1 == self.dist[lb as usize] && self.dfs(lb)) {
                 self.rtol[r] = l;
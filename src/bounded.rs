@@ -3,15 +3,14 @@ pub trait Bounded {
     fn max_value() -> Self;
 }
 
-/*
 macro_rules! bounded {
     ($ty:ty) => {
         impl Bounded for $ty {
             fn min_value() -> Self {
-                <$ty>::min_value()
+                <$ty>::MIN
             }
             fn max_value() -> Self {
-                <$ty>::min_value()
+                <$ty>::MAX
             }
         }
     };
@@ -22,4 +21,3 @@ macro_rules! impls {
 }
 
 impls!(usize, u8, u16, u32, u64, u128, isize, i8, i16, i32, i64, i128, f32, f64);
-*/
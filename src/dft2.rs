@@ -1,4 +1,4 @@
-use crate::modint2::*;
+use crate::{int::Garner, modint2::*};
 
 pub fn dft<M: Modulo>(a: &mut [ModInt<M>]) {
     dft_impl(a, false);
@@ -163,7 +163,117 @@ fn dft_radix4<M: Modulo>(a: &mut [ModInt<M>], inv: bool) {
 
 fn primitive_root<M: Modulo>() -> u32 {
     match M::modulo() {
-        998244353 => 3,
-        _ => todo!(),
+        998244353 | 167772161 | 469762049 => 3,
+        754974721 => 11,
+        _ => M::primitive_root(),
     }
 }
+
+const P1: u32 = 754974721;
+const P2: u32 = 167772161;
+const P3: u32 = 469762049;
+
+fn convolution_prime<const P: u32, T: Copy>(a: &[T], b: &[T]) -> Vec<Mint<P>>
+where
+    Mint<P>: From<T>,
+{
+    let n = a.len() + b.len() - 1;
+    let len = n.next_power_of_two();
+    let mut x: Vec<Mint<P>> = a.iter().map(|&v| Mint::from(v)).collect();
+    let mut y: Vec<Mint<P>> = b.iter().map(|&v| Mint::from(v)).collect();
+    x.resize(len, Mint::ZERO);
+    y.resize(len, Mint::ZERO);
+    dft(&mut x);
+    dft(&mut y);
+    for (x, y) in x.iter_mut().zip(&y) {
+        *x *= y;
+    }
+    idft(&mut x);
+    x.truncate(n);
+    x
+}
+
+/// Convolution modulo `P1 * P2 * P3` (about 2^85.6), as digits `(d0, d1, d2)`
+/// with value `d0 + P1 * d1 + P1 * P2 * d2`.
+fn convolution_three_primes<T: Copy>(a: &[T], b: &[T]) -> Vec<(u64, u64, u64)>
+where
+    Mint<P1>: From<T>,
+    Mint<P2>: From<T>,
+    Mint<P3>: From<T>,
+{
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+    assert!(a.len() + b.len() - 1 <= 1 << 24, "too long");
+    let c1 = convolution_prime::<P1, T>(a, b);
+    let c2 = convolution_prime::<P2, T>(a, b);
+    let c3 = convolution_prime::<P3, T>(a, b);
+    let garner = Garner::new([P1 as u64, P2 as u64, P3 as u64]);
+    c1.iter()
+        .zip(&c2)
+        .zip(&c3)
+        .map(|((r1, r2), r3)| {
+            let [d0, d1, d2] = garner.digits([r1.get() as u64, r2.get() as u64, r3.get() as u64]);
+            (d0, d1, d2)
+        })
+        .collect()
+}
+
+/// Convolution modulo any `m`. Panics unless `min(len) * (m - 1)^2` is below
+/// `P1 * P2 * P3` (about 2^85.6), e.g. about 2^21.6 terms for `m` near 2^32.
+pub fn convolution_any_mod(a: &[u32], b: &[u32], m: u32) -> Vec<u32> {
+    let a: Vec<u32> = a.iter().map(|&x| x % m).collect();
+    let b: Vec<u32> = b.iter().map(|&x| x % m).collect();
+    let p = P1 as u128 * P2 as u128 * P3 as u128;
+    let max = (m as u128 - 1).pow(2);
+    assert!(a.len().min(b.len()) as u128 * max < p, "too long");
+    let m = m as u64;
+    let p1 = P1 as u64 % m;
+    let p12 = P1 as u64 * P2 as u64 % m;
+    convolution_three_primes(&a, &b)
+        .into_iter()
+        .map(|(d0, d1, d2)| ((d0 % m + p1 * (d1 % m) % m + p12 * (d2 % m)) % m) as u32)
+        .collect()
+}
+
+/// `convolution_any_mod` for `ModInt<M>`, which works with `VarMod` too.
+pub fn convolution_mod<M: Modulo>(a: &[ModInt<M>], b: &[ModInt<M>]) -> Vec<ModInt<M>> {
+    let a: Vec<u32> = a.iter().map(|x| x.get()).collect();
+    let b: Vec<u32> = b.iter().map(|x| x.get()).collect();
+    convolution_any_mod(&a, &b, M::modulo())
+        .into_iter()
+        .map(ModInt::unnormalized)
+        .collect()
+}
+
+/// Convolution modulo 2^64. Exact if the true values are below about 2^85.6.
+pub fn convolution_u64(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let p1 = P1 as u64;
+    let p12 = P1 as u64 * P2 as u64;
+    convolution_three_primes(a, b)
+        .into_iter()
+        .map(|(d0, d1, d2)| {
+            d0.wrapping_add(p1.wrapping_mul(d1))
+                .wrapping_add(p12.wrapping_mul(d2))
+        })
+        .collect()
+}
+
+/// Exact convolution if every true value is below about 2^84.6 in absolute
+/// value.
+pub fn convolution_i128(a: &[i64], b: &[i64]) -> Vec<i128> {
+    let p1 = P1 as i128;
+    let p12 = P1 as i128 * P2 as i128;
+    let p = p12 * P3 as i128;
+    convolution_three_primes(a, b)
+        .into_iter()
+        .map(|(d0, d1, d2)| {
+            let x = d0 as i128 + p1 * d1 as i128 + p12 * d2 as i128;
+            if x > p / 2 {
+                x - p
+            } else {
+                x
+            }
+        })
+        .collect()
+}
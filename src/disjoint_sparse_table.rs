@@ -1,14 +1,95 @@
-/*
+use std::ops::{Bound, RangeBounds};
+
+use crate::algebra::Monoid;
+
+/// Static range product for any associative `f` in O(1) per query.
 pub struct DisjointSparseTable<T, F> {
     len: usize,
     f: F,
     data: Vec<T>,
 }
 
-impl DisjointSparseTable<T, F: Fn(&T, &T) -> T> {
-    pub fn new(mut a: Vec<T>, f: F) -> Self {
+impl<T: Clone, F: Fn(&T, &T) -> T> DisjointSparseTable<T, F> {
+    pub fn new(a: Vec<T>, f: F) -> Self {
         let len = a.len();
-        let h = (0usize.leading_zeros() - (len - 1).leading_zeros()).max(1);
+        let h = (usize::BITS - len.saturating_sub(1).leading_zeros()).max(1) as usize;
+        let mut data = a;
+        data.reserve(len * (h - 1));
+        for k in 1..h {
+            let half = 1 << k;
+            let mut level = data[..len].to_vec();
+            for s in (0..len).step_by(2 * half) {
+                let mid = (s + half).min(len);
+                let end = (s + 2 * half).min(len);
+                for i in (s..mid.saturating_sub(1)).rev() {
+                    level[i] = f(&level[i], &level[i + 1]);
+                }
+                for i in mid + 1..end {
+                    level[i] = f(&level[i - 1], &level[i]);
+                }
+            }
+            data.extend(level);
+        }
+        Self { len, f, data }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Panics if `range` is empty.
+    pub fn query<R: RangeBounds<usize>>(&self, range: R) -> T {
+        self.try_query(range).expect("empty range")
+    }
+
+    pub fn try_query<R: RangeBounds<usize>>(&self, range: R) -> Option<T> {
+        let l = match range.start_bound() {
+            Bound::Included(&i) => i,
+            Bound::Excluded(&i) => i + 1,
+            Bound::Unbounded => 0,
+        };
+        let r = match range.end_bound() {
+            Bound::Included(&i) => i + 1,
+            Bound::Excluded(&i) => i,
+            Bound::Unbounded => self.len,
+        };
+        assert!(l <= r && r <= self.len, "out of range");
+        if l == r {
+            return None;
+        }
+        let r = r - 1;
+        if l == r {
+            return Some(self.data[l].clone());
+        }
+        let k = (usize::BITS - 1 - (l ^ r).leading_zeros()) as usize;
+        let level = &self.data[k * self.len..(k + 1) * self.len];
+        Some((self.f)(&level[l], &level[r]))
+    }
+}
+
+impl<T: Monoid + Clone> DisjointSparseTable<T, fn(&T, &T) -> T> {
+    pub fn from_monoid(a: Vec<T>) -> Self {
+        Self::new(a, T::op)
+    }
+
+    /// Returns `T::id()` for an empty range.
+    pub fn prod<R: RangeBounds<usize>>(&self, range: R) -> T {
+        self.try_query(range).unwrap_or_else(T::id)
+    }
+}
+
+impl<T: Monoid + Clone> From<Vec<T>> for DisjointSparseTable<T, fn(&T, &T) -> T> {
+    fn from(a: Vec<T>) -> Self {
+        Self::from_monoid(a)
+    }
+}
+
+impl<T: Monoid + Clone> FromIterator<T> for DisjointSparseTable<T, fn(&T, &T) -> T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from_monoid(iter.into_iter().collect())
     }
 }
-*/
@@ -164,3 +164,252 @@ impl<T, F> Drop for DsuMerge<T, F> {
         }
     }
 }
+
+/// `Dsu` that records every modification so that unions can be undone.
+#[derive(Clone, Debug)]
+pub struct RollbackDsu {
+    inner: Dsu,
+    count: usize,
+    history: Vec<(usize, isize)>,
+}
+
+impl RollbackDsu {
+    pub fn new(n: usize) -> Self {
+        Self {
+            inner: Dsu::new(n),
+            count: n,
+            history: vec![],
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    pub fn root(&self, u: usize) -> usize {
+        self.inner.root(u)
+    }
+    pub fn is_root(&self, u: usize) -> bool {
+        self.inner.is_root(u)
+    }
+    pub fn unite(&mut self, u: usize, v: usize) -> UniteResult {
+        let ru = self.root(u);
+        let rv = self.root(v);
+        if ru != rv {
+            self.history.push((ru, self.inner.0[ru]));
+            self.history.push((rv, self.inner.0[rv]));
+            self.count -= 1;
+        }
+        self.inner.unite(ru, rv)
+    }
+    pub fn is_same(&self, u: usize, v: usize) -> bool {
+        self.inner.is_same(u, v)
+    }
+    pub fn size(&self, u: usize) -> usize {
+        self.inner.size(u)
+    }
+    /// Number of components.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+    pub fn components(&self) -> Components<'_> {
+        self.inner.components()
+    }
+    pub fn snapshot(&self) -> usize {
+        self.history.len()
+    }
+    /// Undoes every union made after `snapshot` was taken.
+    pub fn rollback(&mut self, snapshot: usize) {
+        assert!(snapshot <= self.history.len());
+        while self.history.len() > snapshot {
+            let (rv, v) = self.history.pop().unwrap();
+            let (ru, u) = self.history.pop().unwrap();
+            self.inner.0[rv] = v;
+            self.inner.0[ru] = u;
+            self.count += 1;
+        }
+    }
+}
+
+use crate::algebra::Group;
+
+/// Dsu with potentials. `diff(u, v)` is `p(u)^-1 * p(v)`.
+#[derive(Clone, Debug)]
+pub struct WeightedDsu<G> {
+    inner: Dsu,
+    weight: Vec<G>,
+}
+
+impl<G: Group + Clone + PartialEq> WeightedDsu<G> {
+    pub fn new(n: usize) -> Self {
+        Self {
+            inner: Dsu::new(n),
+            weight: vec![G::id(); n],
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    pub fn root(&self, u: usize) -> usize {
+        self.inner.root(u)
+    }
+    /// Root of `u` and the potential of `u` relative to it.
+    fn potential(&self, mut u: usize) -> (usize, G) {
+        let mut p = G::id();
+        while self.inner.0[u] >= 0 {
+            p = self.weight[u].op(&p);
+            u = self.inner.0[u] as usize;
+        }
+        (u, p)
+    }
+    /// Adds the constraint `diff(u, v) == w`. Returns `None` if it contradicts
+    /// the existing constraints, which are left unchanged in that case.
+    pub fn unite(&mut self, u: usize, v: usize, w: G) -> Option<UniteResult> {
+        let (ru, pu) = self.potential(u);
+        let (rv, pv) = self.potential(v);
+        if ru == rv {
+            return if pu.op(&w) == pv {
+                Some(self.inner.unite(ru, rv))
+            } else {
+                None
+            };
+        }
+        let res = self.inner.unite(ru, rv);
+        if res.root == ru {
+            self.weight[rv] = pu.op(&w).op(&pv.inv());
+        } else {
+            self.weight[ru] = pv.op(&w.inv()).op(&pu.inv());
+        }
+        Some(res)
+    }
+    pub fn is_same(&self, u: usize, v: usize) -> bool {
+        self.inner.is_same(u, v)
+    }
+    pub fn size(&self, u: usize) -> usize {
+        self.inner.size(u)
+    }
+    /// `None` if `u` and `v` are not connected.
+    pub fn diff(&self, u: usize, v: usize) -> Option<G> {
+        let (ru, pu) = self.potential(u);
+        let (rv, pv) = self.potential(v);
+        if ru == rv {
+            Some(pu.inv().op(&pv))
+        } else {
+            None
+        }
+    }
+}
+
+use std::collections::HashMap;
+
+/// Offline dynamic connectivity over a segment tree on time.
+///
+/// Queries are numbered in the order `query` is called. Each edge is alive
+/// from its `add_edge` call until its `remove_edge` call.
+pub struct OfflineDynamicConnectivity {
+    n: usize,
+    num_queries: usize,
+    open: HashMap<(usize, usize), Vec<usize>>,
+    spans: Vec<(usize, usize, usize, usize)>,
+}
+
+impl OfflineDynamicConnectivity {
+    pub fn new(n: usize) -> Self {
+        Self {
+            n,
+            num_queries: 0,
+            open: HashMap::new(),
+            spans: vec![],
+        }
+    }
+    fn key(u: usize, v: usize) -> (usize, usize) {
+        (u.min(v), u.max(v))
+    }
+    pub fn add_edge(&mut self, u: usize, v: usize) {
+        self.open
+            .entry(Self::key(u, v))
+            .or_default()
+            .push(self.num_queries);
+    }
+    /// Panics if the edge is not present.
+    pub fn remove_edge(&mut self, u: usize, v: usize) {
+        let (u, v) = Self::key(u, v);
+        let l = self
+            .open
+            .get_mut(&(u, v))
+            .and_then(|s| s.pop())
+            .expect("no such edge");
+        if l < self.num_queries {
+            self.spans.push((l, self.num_queries, u, v));
+        }
+    }
+    /// Returns the index of the new query.
+    pub fn query(&mut self) -> usize {
+        self.num_queries += 1;
+        self.num_queries - 1
+    }
+    /// Calls `f(i, dsu)` for each query `i` in order, where `dsu` holds the
+    /// edges alive at that query.
+    pub fn run(mut self, mut f: impl FnMut(usize, &RollbackDsu)) {
+        let q = self.num_queries;
+        if q == 0 {
+            return;
+        }
+        for (&(u, v), starts) in &self.open {
+            for &l in starts {
+                if l < q {
+                    self.spans.push((l, q, u, v));
+                }
+            }
+        }
+        let size = q.next_power_of_two();
+        let mut seg = vec![vec![]; 2 * size];
+        for &(l, r, u, v) in &self.spans {
+            let mut l = l + size;
+            let mut r = r + size;
+            while l < r {
+                if l & 1 == 1 {
+                    seg[l].push((u, v));
+                    l += 1;
+                }
+                if r & 1 == 1 {
+                    r -= 1;
+                    seg[r].push((u, v));
+                }
+                l >>= 1;
+                r >>= 1;
+            }
+        }
+        let mut dsu = RollbackDsu::new(self.n);
+        Self::dfs(1, 0, size, q, &seg, &mut dsu, &mut f);
+    }
+    fn dfs(
+        i: usize,
+        l: usize,
+        r: usize,
+        q: usize,
+        seg: &[Vec<(usize, usize)>],
+        dsu: &mut RollbackDsu,
+        f: &mut impl FnMut(usize, &RollbackDsu),
+    ) {
+        if l >= q {
+            return;
+        }
+        let snapshot = dsu.snapshot();
+        for &(u, v) in &seg[i] {
+            dsu.unite(u, v);
+        }
+        if r - l == 1 {
+            f(l, dsu);
+        } else {
+            let mid = (l + r) / 2;
+            Self::dfs(2 * i, l, mid, q, seg, dsu, f);
+            Self::dfs(2 * i + 1, mid, r, q, seg, dsu, f);
+        }
+        dsu.rollback(snapshot);
+    }
+}
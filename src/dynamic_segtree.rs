@@ -0,0 +1,269 @@
+use std::mem;
+
+pub use crate::algebra::{Action, Monoid};
+
+type Idx = u32;
+const NIL: Idx = !0;
+
+#[derive(Clone)]
+struct Node<T, F> {
+    sum: T,
+    lazy: F,
+    l: Idx,
+    r: Idx,
+}
+
+/// Segment tree over `[lo, hi)` whose nodes are created on demand.
+///
+/// Range application is optional (`F = ()` disables it). In persistent mode,
+/// every update copies the nodes on its path and adds a new version.
+/// `hi - lo` must fit in `i64`.
+pub struct DynamicSegTree<T, F = ()> {
+    lo: i64,
+    hi: i64,
+    init: Option<T>,
+    persistent: bool,
+    nodes: Vec<Node<T, F>>,
+    roots: Vec<Idx>,
+    /// Nodes from this index on were created by the current update and are
+    /// not shared with older versions.
+    fresh: usize,
+}
+
+impl<T: Monoid + Clone, F: Action<T> + Clone + PartialEq> DynamicSegTree<T, F> {
+    /// All values are `T::id()`.
+    pub fn new(lo: i64, hi: i64) -> Self {
+        assert!(lo <= hi && hi.checked_sub(lo).is_some());
+        Self {
+            lo,
+            hi,
+            init: None,
+            persistent: false,
+            nodes: vec![],
+            roots: vec![NIL],
+            fresh: 0,
+        }
+    }
+
+    /// All values are `init`.
+    pub fn with_init(lo: i64, hi: i64, init: T) -> Self {
+        Self {
+            init: Some(init),
+            ..Self::new(lo, hi)
+        }
+    }
+
+    pub fn persistent(mut self) -> Self {
+        self.persistent = true;
+        self
+    }
+
+    /// Number of versions. Version 0 is the initial state and the last one is
+    /// the current state. Always 1 unless persistent.
+    pub fn versions(&self) -> usize {
+        self.roots.len()
+    }
+
+    pub fn latest(&self) -> usize {
+        self.roots.len() - 1
+    }
+
+    /// Makes a copy of version `v` the latest version.
+    pub fn branch(&mut self, v: usize) -> usize {
+        assert!(self.persistent);
+        self.roots.push(self.roots[v]);
+        self.latest()
+    }
+
+    pub fn num_nodes(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn get(&self, i: i64) -> T {
+        self.get_at(self.latest(), i)
+    }
+
+    pub fn get_at(&self, v: usize, i: i64) -> T {
+        self.prod_at(v, i, i + 1)
+    }
+
+    pub fn prod(&self, l: i64, r: i64) -> T {
+        self.prod_at(self.latest(), l, r)
+    }
+
+    pub fn prod_at(&self, v: usize, l: i64, r: i64) -> T {
+        assert!(self.lo <= l && l <= r && r <= self.hi);
+        if l == r {
+            return T::id();
+        }
+        self.prod_rec(self.roots[v], self.lo, self.hi, l, r)
+    }
+
+    fn prod_rec(&self, i: Idx, lo: i64, hi: i64, l: i64, r: i64) -> T {
+        if i == NIL {
+            return self.empty(r.min(hi) - l.max(lo));
+        }
+        let node = &self.nodes[i as usize];
+        if l <= lo && hi <= r {
+            return node.sum.clone();
+        }
+        let mid = lo + (hi - lo) / 2;
+        let x = if r <= mid {
+            self.prod_rec(node.l, lo, mid, l, r)
+        } else if mid <= l {
+            self.prod_rec(node.r, mid, hi, l, r)
+        } else {
+            self.prod_rec(node.l, lo, mid, l, r)
+                .op(&self.prod_rec(node.r, mid, hi, l, r))
+        };
+        node.lazy.act(&x)
+    }
+
+    /// Sets the value at `i` and returns the new version.
+    pub fn set(&mut self, i: i64, x: T) -> usize {
+        self.update(i, |_| x)
+    }
+
+    pub fn update(&mut self, i: i64, f: impl FnOnce(&T) -> T) -> usize {
+        assert!(self.lo <= i && i < self.hi);
+        self.fresh = self.nodes.len();
+        let root = self.update_rec(self.roots[self.latest()], self.lo, self.hi, i, f);
+        self.commit(root)
+    }
+
+    fn update_rec(&mut self, i: Idx, lo: i64, hi: i64, k: i64, f: impl FnOnce(&T) -> T) -> Idx {
+        let i = self.touch(i, hi - lo);
+        if hi - lo == 1 {
+            let node = &mut self.nodes[i as usize];
+            node.sum = f(&node.sum);
+            return i;
+        }
+        let mid = lo + (hi - lo) / 2;
+        self.push(i, hi - lo);
+        if k < mid {
+            let c = self.update_rec(self.nodes[i as usize].l, lo, mid, k, f);
+            self.nodes[i as usize].l = c;
+        } else {
+            let c = self.update_rec(self.nodes[i as usize].r, mid, hi, k, f);
+            self.nodes[i as usize].r = c;
+        }
+        self.pull(i, lo, hi);
+        i
+    }
+
+    /// Applies `f` to `[l, r)` and returns the new version.
+    pub fn apply(&mut self, l: i64, r: i64, f: &F) -> usize {
+        assert!(self.lo <= l && l <= r && r <= self.hi);
+        self.fresh = self.nodes.len();
+        let root = self.roots[self.latest()];
+        let root = if l < r {
+            self.apply_rec(root, self.lo, self.hi, l, r, f)
+        } else {
+            root
+        };
+        self.commit(root)
+    }
+
+    fn apply_rec(&mut self, i: Idx, lo: i64, hi: i64, l: i64, r: i64, f: &F) -> Idx {
+        let i = self.touch(i, hi - lo);
+        if l <= lo && hi <= r {
+            let node = &mut self.nodes[i as usize];
+            node.sum = f.act(&node.sum);
+            node.lazy = f.op(&node.lazy);
+            return i;
+        }
+        let mid = lo + (hi - lo) / 2;
+        self.push(i, hi - lo);
+        if l < mid {
+            let c = self.apply_rec(self.nodes[i as usize].l, lo, mid, l, r, f);
+            self.nodes[i as usize].l = c;
+        }
+        if mid < r {
+            let c = self.apply_rec(self.nodes[i as usize].r, mid, hi, l, r, f);
+            self.nodes[i as usize].r = c;
+        }
+        self.pull(i, lo, hi);
+        i
+    }
+
+    fn commit(&mut self, root: Idx) -> usize {
+        if self.persistent {
+            self.roots.push(root);
+        } else {
+            *self.roots.last_mut().unwrap() = root;
+        }
+        self.latest()
+    }
+
+    fn empty(&self, len: i64) -> T {
+        match &self.init {
+            None => T::id(),
+            Some(x) => {
+                let mut res = T::id();
+                let mut x = x.clone();
+                let mut len = len;
+                while len > 0 {
+                    if len & 1 == 1 {
+                        res = res.op(&x);
+                    }
+                    x = x.op(&x);
+                    len >>= 1;
+                }
+                res
+            }
+        }
+    }
+
+    /// Returns a node that may be modified in place.
+    fn touch(&mut self, i: Idx, len: i64) -> Idx {
+        if i == NIL {
+            let node = Node {
+                sum: self.empty(len),
+                lazy: F::id(),
+                l: NIL,
+                r: NIL,
+            };
+            self.nodes.push(node);
+        } else if self.persistent && (i as usize) < self.fresh {
+            self.nodes.push(self.nodes[i as usize].clone());
+        } else {
+            return i;
+        }
+        (self.nodes.len() - 1) as Idx
+    }
+
+    fn push(&mut self, i: Idx, len: i64) {
+        if self.nodes[i as usize].lazy == F::id() {
+            return;
+        }
+        let f = mem::replace(&mut self.nodes[i as usize].lazy, F::id());
+        let l = self.touch(self.nodes[i as usize].l, len / 2);
+        let r = self.touch(self.nodes[i as usize].r, len - len / 2);
+        for c in [l, r] {
+            let child = &mut self.nodes[c as usize];
+            child.sum = f.act(&child.sum);
+            child.lazy = f.op(&child.lazy);
+        }
+        let node = &mut self.nodes[i as usize];
+        node.l = l;
+        node.r = r;
+    }
+
+    fn pull(&mut self, i: Idx, lo: i64, hi: i64) {
+        let mid = lo + (hi - lo) / 2;
+        let node = &self.nodes[i as usize];
+        let sum = self
+            .child_sum(node.l, mid - lo)
+            .op(&self.child_sum(node.r, hi - mid));
+        let sum = node.lazy.act(&sum);
+        self.nodes[i as usize].sum = sum;
+    }
+
+    fn child_sum(&self, i: Idx, len: i64) -> T {
+        if i == NIL {
+            self.empty(len)
+        } else {
+            self.nodes[i as usize].sum.clone()
+        }
+    }
+}
@@ -1,20 +1,13 @@
 use std::mem;
 
-pub trait Monoid {
-    fn id() -> Self;
-    fn op(&self, other: &Self) -> Self;
-}
-
-pub trait Map<T> {
-    fn map(&self, value: &T) -> T;
-}
+pub use crate::algebra::{Action, Monoid};
 
 pub struct LazySegTree<T, F> {
     a: Vec<T>,
     f: Vec<F>,
 }
 
-impl<T: Monoid, F: Map<T> + Monoid> LazySegTree<T, F> {
+impl<T: Monoid, F: Action<T>> LazySegTree<T, F> {
     pub fn new(len: usize) -> Self {
         Self {
             a: (0..2 * len).map(|_| T::id()).collect(),
@@ -43,7 +36,7 @@ impl<T: Monoid, F: Map<T> + Monoid> LazySegTree<T, F> {
                 l += 1;
                 l >>= l.trailing_zeros();
                 while i > l / 2 {
-                    sum_l = self.f[i].map(&sum_l);
+                    sum_l = self.f[i].act(&sum_l);
                     i /= 2;
                 }
             } else {
@@ -52,7 +45,7 @@ impl<T: Monoid, F: Map<T> + Monoid> LazySegTree<T, F> {
                 sum_r = self.a[r].op(&sum_r);
                 r >>= r.trailing_zeros();
                 while i > r / 2 {
-                    sum_r = self.f[i].map(&sum_r);
+                    sum_r = self.f[i].act(&sum_r);
                     i /= 2;
                 }
             }
@@ -63,7 +56,7 @@ impl<T: Monoid, F: Map<T> + Monoid> LazySegTree<T, F> {
         let mut sum = sum_l.op(&sum_r);
         let mut i = l / 2;
         while i > 0 {
-            sum = self.f[i].map(&sum);
+            sum = self.f[i].act(&sum);
             i /= 2;
         }
         sum
@@ -87,7 +80,7 @@ impl<T: Monoid, F: Map<T> + Monoid> LazySegTree<T, F> {
         self.propagate(p1, d1);
         loop {
             if l >= r {
-                self.a[l] = f.map(&self.a[l]);
+                self.a[l] = f.act(&self.a[l]);
                 if let Some(fl) = self.f.get_mut(l) {
                     *fl = f.op(fl);
                 }
@@ -95,7 +88,7 @@ impl<T: Monoid, F: Map<T> + Monoid> LazySegTree<T, F> {
                 l >>= l.trailing_zeros();
             } else {
                 r -= 1;
-                self.a[r] = f.map(&self.a[r]);
+                self.a[r] = f.act(&self.a[r]);
                 if let Some(fr) = self.f.get_mut(r) {
                     *fr = f.op(fr);
                 }
@@ -115,11 +108,11 @@ impl<T: Monoid, F: Map<T> + Monoid> LazySegTree<T, F> {
             let l = 2 * p;
             let r = 2 * p + 1;
             let f = mem::replace(&mut self.f[p], F::id());
-            self.a[l] = f.map(&self.a[l]);
+            self.a[l] = f.act(&self.a[l]);
             if let Some(fl) = self.f.get_mut(l) {
                 *fl = f.op(fl);
             }
-            self.a[r] = f.map(&self.a[r]);
+            self.a[r] = f.act(&self.a[r]);
             if let Some(fr) = self.f.get_mut(r) {
                 *fr = f.op(fr);
             }
@@ -177,6 +170,7 @@ impl<T: Monoid, F: Monoid> From<Vec<T>> for LazySegTree<T, F> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::algebra::Sum;
     use crate::modint2::*;
     use crate::random::*;
 
@@ -257,7 +251,12 @@ mod tests {
 
     fn affine_sum_impl(init: Vec<Mint<M>>, cmd: impl IntoIterator<Item = Cmd>) {
         let mut naive = init;
-        let mut st = LazySegTree::from(naive.iter().map(|x| (*x, mint(1))).collect::<Vec<_>>());
+        let mut st = LazySegTree::from(
+            naive
+                .iter()
+                .map(|x| (Sum(*x), Sum(mint(1))))
+                .collect::<Vec<_>>(),
+        );
         for cmd in cmd {
             match cmd {
                 Cmd::Sum { l, r } => {
@@ -265,7 +264,7 @@ mod tests {
                     for a in &naive[l..r] {
                         sum_naive += a;
                     }
-                    assert_eq!(st.sum(l, r).0, sum_naive, "l={l} r={r}");
+                    assert_eq!(st.sum(l, r).0 .0, sum_naive, "l={l} r={r}");
                 }
                 Cmd::Apply { l, r, f } => {
                     st.apply(l, r, f);
@@ -283,34 +282,8 @@ mod tests {
     }
 
     const M: u32 = 998244353;
-    impl Monoid for (Mint<M>, Mint<M>) {
-        fn id() -> Self {
-            (mint(0), mint(0))
-        }
-        fn op(&self, other: &Self) -> Self {
-            (self.0 + other.0, self.1 + other.1)
-        }
-    }
-    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
-    struct Affine {
-        a: Mint<M>,
-        b: Mint<M>,
-    }
+    type Affine = crate::algebra::Affine<ConstMod<M>>;
     fn affine(a: Mint<M>, b: Mint<M>) -> Affine {
-        Affine { a, b }
-    }
-    impl Monoid for Affine {
-        fn id() -> Self {
-            affine(mint(1), mint(0))
-        }
-        fn op(&self, other: &Self) -> Self {
-            // a_l(a_r x + b_r) + b_l
-            affine(self.a * other.a, self.a * other.b + self.b)
-        }
-    }
-    impl Map<(Mint<M>, Mint<M>)> for Affine {
-        fn map(&self, x: &(Mint<M>, Mint<M>)) -> (Mint<M>, Mint<M>) {
-            (self.a * x.0 + self.b * x.1, x.1)
-        }
+        Affine::new(a, b)
     }
 }
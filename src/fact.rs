@@ -1,4 +1,8 @@
 use super::mod_int::*;
+use crate::{
+    modint2::{Fact as Fact2, ModInt as ModInt2, Modulo as Modulo2, Pow as _},
+    modint_poly::Poly,
+};
 use std::cell::RefCell;
 
 pub struct FixedFact<M> {
@@ -52,6 +56,41 @@ impl<M: Modulo> FixedFact<M> {
             ModInt::new(0)
         }
     }
+    /// `(k_1 + ... + k_m)! / (k_1! ... k_m!)`.
+    pub fn multinomial(&self, ks: &[usize]) -> ModInt<M> {
+        let n = ks.iter().sum();
+        ks.iter()
+            .fold(self.fact(n), |acc, &k| acc * self.fact_inv(k))
+    }
+    /// `binom(n, k)` for any integer `n`, using `binom(-n, k) = (-1)^k binom(n + k - 1, k)`.
+    #[allow(clippy::manual_is_multiple_of)]
+    pub fn binom_signed(&self, n: i64, k: usize) -> ModInt<M> {
+        if n >= 0 {
+            self.binom(n as usize, k)
+        } else {
+            let b = self.binom(n.unsigned_abs() as usize + k - 1, k);
+            if k % 2 == 0 {
+                b
+            } else {
+                -b
+            }
+        }
+    }
+    pub fn catalan(&self, n: usize) -> ModInt<M> {
+        self.fact(2 * n) * self.fact_inv(n + 1) * self.fact_inv(n)
+    }
+    /// `binom(n, k)` by Lucas' theorem for a prime modulus `p`. The table
+    /// must cover `min(n, p - 1)`.
+    pub fn binom_lucas(&self, mut n: u64, mut k: u64) -> ModInt<M> {
+        let p = M::modulo() as u64;
+        let mut res = ModInt::new(1);
+        while k > 0 && res.get() != 0 {
+            res *= self.binom((n % p) as usize, (k % p) as usize);
+            n /= p;
+            k /= p;
+        }
+        res
+    }
 }
 
 pub struct Fact<M>(RefCell<FixedFact<M>>);
@@ -79,6 +118,25 @@ impl<M: Modulo> Fact<M> {
             ModInt::new(0)
         }
     }
+    pub fn multinomial(&self, ks: &[usize]) -> ModInt<M> {
+        let n = ks.iter().sum();
+        self.0.borrow_mut().grow(n).multinomial(ks)
+    }
+    pub fn binom_signed(&self, n: i64, k: usize) -> ModInt<M> {
+        let m = if n >= 0 {
+            n as usize
+        } else {
+            n.unsigned_abs() as usize + k - 1
+        };
+        self.0.borrow_mut().grow(m).binom_signed(n, k)
+    }
+    pub fn catalan(&self, n: usize) -> ModInt<M> {
+        self.0.borrow_mut().grow(2 * n).catalan(n)
+    }
+    pub fn binom_lucas(&self, n: u64, k: u64) -> ModInt<M> {
+        let m = n.min(M::modulo() as u64 - 1) as usize;
+        self.0.borrow_mut().grow(m).binom_lucas(n, k)
+    }
 }
 
 impl<M: Modulo> Default for Fact<M> {
@@ -86,3 +144,89 @@ impl<M: Modulo> Default for Fact<M> {
         Self::new()
     }
 }
+
+/// Unsigned Stirling numbers of the first kind `[n, k]` for `k` in `0..=n`,
+/// the coefficients of `x (x + 1) ... (x + n - 1)`, in O(n log n).
+pub fn stirling1_row<M: Modulo2>(n: usize) -> Vec<ModInt2<M>> {
+    fn rising<M: Modulo2>(n: usize) -> Poly<M> {
+        if n == 0 {
+            return Poly::from(vec![ModInt2::ONE]);
+        }
+        let h = rising::<M>(n / 2);
+        let mut res = &h * h.taylor_shift(ModInt2::from(n / 2));
+        if n % 2 == 1 {
+            res *= Poly::from(vec![ModInt2::from(n - 1), ModInt2::ONE]);
+        }
+        res
+    }
+    let mut res = rising::<M>(n).to_vec();
+    res.resize(n + 1, ModInt2::ZERO);
+    res
+}
+
+/// Stirling numbers of the second kind `{n, k}` for `k` in `0..=n`, in
+/// O(n log n).
+pub fn stirling2_row<M: Modulo2>(n: usize) -> Vec<ModInt2<M>> {
+    let fact = Fact2::<M>::new();
+    let a: Poly<M> = (0..=n)
+        .map(|i| {
+            let x = fact.fact_inv(i);
+            if i % 2 == 0 {
+                x
+            } else {
+                -x
+            }
+        })
+        .collect();
+    let b: Poly<M> = (0..=n)
+        .map(|i| ModInt2::<M>::from(i).pow(n) * fact.fact_inv(i))
+        .collect();
+    let mut res = (a * b).to_vec();
+    res.resize(n + 1, ModInt2::ZERO);
+    res
+}
+
+/// Bell numbers `B_0, ..., B_n` from `exp(e^x - 1)`.
+pub fn bell_numbers<M: Modulo2>(n: usize) -> Vec<ModInt2<M>> {
+    let fact = Fact2::<M>::new();
+    let f: Poly<M> = (0..=n)
+        .map(|i| {
+            if i == 0 {
+                ModInt2::ZERO
+            } else {
+                fact.fact_inv(i)
+            }
+        })
+        .collect();
+    let mut res = f.exp(n + 1).to_vec();
+    res.resize(n + 1, ModInt2::ZERO);
+    for (i, x) in res.iter_mut().enumerate() {
+        *x *= fact.fact(i);
+    }
+    res
+}
+
+/// Partition numbers `p(0), ..., p(n)` as the inverse of Euler's
+/// pentagonal series.
+pub fn partition_numbers<M: Modulo2>(n: usize) -> Vec<ModInt2<M>> {
+    let mut f = vec![ModInt2::ZERO; n + 1];
+    f[0] = ModInt2::ONE;
+    for k in 1.. {
+        let sign = if k % 2 == 0 {
+            ModInt2::ONE
+        } else {
+            -ModInt2::ONE
+        };
+        let (a, b) = (k * (3 * k - 1) / 2, k * (3 * k + 1) / 2);
+        if a > n {
+            break;
+        }
+        f[a] += sign;
+        if b <= n {
+            f[b] += sign;
+        }
+    }
+    let mut res = Poly::from(f).inv(n + 1).to_vec();
+    res.resize(n + 1, ModInt2::ZERO);
+    res
+}
@@ -1,3 +1,8 @@
+use crate::{
+    int::gcd,
+    montgomery::{Montgomery64, MontgomeryReduction},
+};
+
 pub fn factorize_naive(n: u64) -> FactorizeNaive {
     FactorizeNaive { n, i: 0 }
 }
@@ -7,12 +12,13 @@ pub struct FactorizeNaive {
 }
 impl Iterator for FactorizeNaive {
     type Item = (u64, u32);
+    #[allow(clippy::manual_is_multiple_of)]
     fn next(&mut self) -> Option<(u64, u32)> {
         loop {
             let d = if self.i <= 2 {
                 [2u8, 3, 5][self.i] as u64
             } else {
-                let i = self.i as usize - 2;
+                let i = self.i - 2;
                 i as u64 / 8 * 30 + [1u8, 7, 11, 13, 17, 19, 23, 29][i % 8] as u64
             };
             if d * d > self.n {
@@ -37,3 +43,229 @@ impl Iterator for FactorizeNaive {
         }
     }
 }
+
+/// Plain `u128` arithmetic for odd moduli of 2^63 and above, where
+/// `Montgomery64` does not apply.
+#[derive(Clone, Copy)]
+struct Wide64(u64);
+
+impl MontgomeryReduction for Wide64 {
+    type Int = u64;
+    fn modulo(&self) -> u64 {
+        self.0
+    }
+    fn mul(&self, a: u64, b: u64) -> u64 {
+        (a as u128 * b as u128 % self.0 as u128) as u64
+    }
+    fn add(&self, a: u64, b: u64) -> u64 {
+        let (s, of) = a.overflowing_add(b);
+        if of || s >= self.0 {
+            s.wrapping_sub(self.0)
+        } else {
+            s
+        }
+    }
+    fn sub(&self, a: u64, b: u64) -> u64 {
+        let (d, of) = a.overflowing_sub(b);
+        if of {
+            d.wrapping_add(self.0)
+        } else {
+            d
+        }
+    }
+    fn encode(&self, x: u64) -> u64 {
+        x % self.0
+    }
+    fn decode(&self, a: u64) -> u64 {
+        a
+    }
+}
+
+/// Calls `f` with a reduction for the odd modulus `n`.
+macro_rules! with_reduction {
+    ($n:expr, |$r:ident| $body:expr) => {
+        if $n < 1 << 63 {
+            let $r = Montgomery64::new($n);
+            $body
+        } else {
+            let $r = Wide64($n);
+            $body
+        }
+    };
+}
+
+fn pow<R: MontgomeryReduction<Int = u64>>(r: R, mut a: u64, mut e: u64) -> u64 {
+    let mut res = r.encode(1);
+    while e > 0 {
+        if e & 1 == 1 {
+            res = r.mul(res, a);
+        }
+        a = r.mul(a, a);
+        e >>= 1;
+    }
+    res
+}
+
+fn miller_rabin<R: MontgomeryReduction<Int = u64>>(r: R) -> bool {
+    let n = r.modulo();
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    let one = r.encode(1);
+    let minus_one = r.encode(n - 1);
+    // Deterministic for all n < 2^64.
+    [2, 325, 9375, 28178, 450775, 9780504, 1795265022]
+        .iter()
+        .all(|&a| {
+            let a = r.encode(a);
+            if a == 0 {
+                return true;
+            }
+            let mut x = pow(r, a, d);
+            if x == one || x == minus_one {
+                return true;
+            }
+            for _ in 1..s {
+                x = r.mul(x, x);
+                if x == minus_one {
+                    return true;
+                }
+            }
+            false
+        })
+}
+
+#[allow(clippy::manual_is_multiple_of)]
+pub fn is_prime_u64(n: u64) -> bool {
+    if n < 64 {
+        return 1u64 << n & 0x28208a20a08a28ac != 0;
+    }
+    if n % 2 == 0 || n % 3 == 0 || n % 5 == 0 || n % 7 == 0 {
+        return false;
+    }
+    with_reduction!(n, |r| miller_rabin(r))
+}
+
+/// A nontrivial factor of the odd composite `n` by Pollard's rho with
+/// Brent's cycle detection.
+fn find_factor<R: MontgomeryReduction<Int = u64>>(r: R) -> u64 {
+    const BATCH: u64 = 128;
+    let n = r.modulo();
+    let one = r.encode(1);
+    for c in 1.. {
+        let c = r.encode(c);
+        let f = |x| r.add(r.mul(x, x), c);
+        let (mut x, mut y, mut ys) = (one, one, one);
+        let mut q = one;
+        let mut g = 1;
+        let mut len = 1;
+        while g == 1 {
+            x = y;
+            for _ in 0..len {
+                y = f(y);
+            }
+            let mut k = 0;
+            while k < len && g == 1 {
+                ys = y;
+                for _ in 0..BATCH.min(len - k) {
+                    y = f(y);
+                    q = r.mul(q, r.sub(x, y));
+                }
+                g = gcd(r.decode(q), n);
+                k += BATCH;
+            }
+            len *= 2;
+        }
+        if g == n {
+            // The batch overshot; redo it one step at a time.
+            loop {
+                ys = f(ys);
+                g = gcd(r.decode(r.sub(x, ys)), n);
+                if g != 1 {
+                    break;
+                }
+            }
+        }
+        if g != n {
+            return g;
+        }
+    }
+    unreachable!()
+}
+
+fn factorize_rec(n: u64, ps: &mut Vec<u64>) {
+    if n == 1 {
+        return;
+    }
+    if is_prime_u64(n) {
+        ps.push(n);
+        return;
+    }
+    let d = with_reduction!(n, |r| find_factor(r));
+    factorize_rec(d, ps);
+    factorize_rec(n / d, ps);
+}
+
+/// Prime factorization as `(p, e)` pairs sorted by `p`, in expected
+/// O(n^(1/4)) multiplications.
+#[allow(clippy::manual_is_multiple_of)]
+pub fn factorize_rho(mut n: u64) -> Vec<(u64, u32)> {
+    assert!(n > 0);
+    let mut ps = vec![];
+    for p in [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37] {
+        while n % p == 0 {
+            n /= p;
+            ps.push(p);
+        }
+    }
+    factorize_rec(n, &mut ps);
+    ps.sort_unstable();
+    let mut res: Vec<(u64, u32)> = vec![];
+    for p in ps {
+        match res.last_mut() {
+            Some((q, e)) if *q == p => *e += 1,
+            _ => res.push((p, 1)),
+        }
+    }
+    res
+}
+
+/// All divisors of `n` in increasing order.
+pub fn divisors(n: u64) -> Vec<u64> {
+    let mut res = vec![1];
+    for (p, e) in factorize_rho(n) {
+        let len = res.len();
+        let mut pk = 1;
+        for _ in 0..e {
+            pk *= p;
+            for i in 0..len {
+                res.push(res[i] * pk);
+            }
+        }
+    }
+    res.sort_unstable();
+    res
+}
+
+pub fn euler_phi(n: u64) -> u64 {
+    factorize_rho(n)
+        .into_iter()
+        .fold(n, |phi, (p, _)| phi / p * (p - 1))
+}
+
+/// Smallest primitive root modulo the prime `p`.
+pub fn primitive_root(p: u64) -> u64 {
+    debug_assert!(is_prime_u64(p));
+    if p == 2 {
+        return 1;
+    }
+    let qs = factorize_rho(p - 1);
+    with_reduction!(p, |r| {
+        let one = r.encode(1);
+        (2..)
+            .find(|&g| {
+                let g = r.encode(g);
+                qs.iter().all(|&(q, _)| pow(r, g, (p - 1) / q) != one)
+            })
+            .unwrap()
+    })
+}
@@ -1,9 +1,14 @@
+use std::ops::{Add, Mul, Sub};
+
+pub use crate::algebra::{CommutativeMonoid, Group, Monoid, Sum};
+use crate::coord_comp::CoordComp;
+
 #[derive(Clone)]
 pub struct FenwickTree<T> {
     a: Vec<T>,
 }
 
-impl<T: Monoid> FenwickTree<T> {
+impl<T: CommutativeMonoid> FenwickTree<T> {
     pub fn new(n: usize) -> Self {
         Self {
             a: (0..n).map(|_| T::id()).collect(),
@@ -32,6 +37,13 @@ impl<T: Monoid> FenwickTree<T> {
         self.sum(self.len())
     }
 
+    pub fn sum_range(&self, l: usize, r: usize) -> T
+    where
+        T: Group,
+    {
+        self.sum(r).op_inv(&self.sum(l))
+    }
+
     pub fn add(&mut self, i: usize, x: T) {
         assert!(
             i < self.a.len(),
@@ -45,9 +57,236 @@ impl<T: Monoid> FenwickTree<T> {
             i += i & (!i + 1);
         }
     }
+    /// Largest `r` such that `pred(sum(r))`. `pred` must be monotone and
+    /// `pred(T::id())` must hold.
+    pub fn max_right(&self, mut pred: impl FnMut(&T) -> bool) -> usize {
+        let n = self.a.len();
+        let mut i = 0;
+        let mut x = T::id();
+        if n == 0 {
+            return 0;
+        }
+        let mut k = 1 << (usize::BITS - 1 - n.leading_zeros());
+        while k > 0 {
+            if i + k <= n {
+                let y = x.op(&self.a[i + k - 1]);
+                if pred(&y) {
+                    i += k;
+                    x = y;
+                }
+            }
+            k >>= 1;
+        }
+        i
+    }
+
+    /// Smallest `i` such that `sum(i + 1) >= w`, or `len()` if there is none.
+    /// The prefix sums must be non-decreasing.
+    pub fn lower_bound(&self, w: &T) -> usize
+    where
+        T: PartialOrd,
+    {
+        self.max_right(|x| x < w)
+    }
+}
+
+/// Range add and range sum with Fenwick trees. The terms added and the
+/// terms subtracted are kept apart so that unsigned `T` works.
+#[derive(Clone)]
+pub struct RangeFenwickTree<T> {
+    pos: FenwickTree<(Sum<T>, Sum<T>)>,
+    neg: FenwickTree<(Sum<T>, Sum<T>)>,
+}
+
+impl<T> RangeFenwickTree<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + TryFrom<usize>,
+{
+    pub fn new(n: usize) -> Self {
+        Self {
+            pos: FenwickTree::new(n),
+            neg: FenwickTree::new(n),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.pos.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pos.is_empty()
+    }
+
+    fn index(i: usize) -> T {
+        T::try_from(i).ok().expect("index overflow")
+    }
+
+    /// Adds `x` to every element in `[l, r)`.
+    pub fn add(&mut self, l: usize, r: usize, x: T) {
+        assert!(l <= r && r <= self.len());
+        // Each tree holds `(c, d)` contributing `c + d * i` to `sum(i)`.
+        if l < self.len() {
+            self.pos.add(l, (Sum(T::default()), Sum(x)));
+            self.neg
+                .add(l, (Sum(x * Self::index(l)), Sum(T::default())));
+        }
+        if r < self.len() {
+            self.pos
+                .add(r, (Sum(x * Self::index(r)), Sum(T::default())));
+            self.neg.add(r, (Sum(T::default()), Sum(x)));
+        }
+    }
+
+    /// Sum of `[0, i)`.
+    pub fn sum(&self, i: usize) -> T {
+        let i = i.min(self.len());
+        let (Sum(c0), Sum(d0)) = self.pos.sum(i);
+        let (Sum(c1), Sum(d1)) = self.neg.sum(i);
+        c0 + d0 * Self::index(i) - (c1 + d1 * Self::index(i))
+    }
+
+    pub fn sum_range(&self, l: usize, r: usize) -> T {
+        self.sum(r) - self.sum(l)
+    }
+}
+
+/// Dense 2D Fenwick tree over `[0, h) x [0, w)`.
+#[derive(Clone)]
+pub struct FenwickTree2D<T> {
+    h: usize,
+    w: usize,
+    a: Vec<T>,
+}
+
+impl<T: CommutativeMonoid> FenwickTree2D<T> {
+    pub fn new(h: usize, w: usize) -> Self {
+        Self {
+            h,
+            w,
+            a: (0..h * w).map(|_| T::id()).collect(),
+        }
+    }
+
+    pub fn height(&self) -> usize {
+        self.h
+    }
+
+    pub fn width(&self) -> usize {
+        self.w
+    }
+
+    /// Sum of `[0, i) x [0, j)`.
+    pub fn sum(&self, i: usize, j: usize) -> T {
+        let mut x = T::id();
+        let mut i = i.min(self.h);
+        while i > 0 {
+            let row = &self.a[(i - 1) * self.w..i * self.w];
+            let mut j = j.min(self.w);
+            while j > 0 {
+                x = row[j - 1].op(&x);
+                j = j & j - 1;
+            }
+            i = i & i - 1;
+        }
+        x
+    }
+
+    /// Sum of `[i0, i1) x [j0, j1)`.
+    pub fn sum_rect(&self, i0: usize, i1: usize, j0: usize, j1: usize) -> T
+    where
+        T: Group,
+    {
+        self.sum(i1, j1)
+            .op(&self.sum(i0, j0))
+            .op_inv(&self.sum(i0, j1).op(&self.sum(i1, j0)))
+    }
+
+    pub fn add(&mut self, i: usize, j: usize, x: T) {
+        assert!(i < self.h && j < self.w, "out of range");
+        let mut i = i + 1;
+        while i <= self.h {
+            let row = &mut self.a[(i - 1) * self.w..i * self.w];
+            let mut j = j + 1;
+            while j <= self.w {
+                row[j - 1] = row[j - 1].op(&x);
+                j += j & (!j + 1);
+            }
+            i += i & (!i + 1);
+        }
+    }
 }
 
-pub trait Monoid {
-    fn id() -> Self;
-    fn op(&self, other: &Self) -> Self;
+/// Offline 2D Fenwick tree over points given in advance.
+///
+/// Each node of the outer tree keeps its own compressed `y` coordinates, so
+/// memory is O(n log n) for n points.
+#[derive(Clone)]
+pub struct SparseFenwickTree2D<K, T> {
+    xs: CoordComp<K>,
+    ys: Vec<CoordComp<K>>,
+    bits: Vec<FenwickTree<T>>,
+}
+
+impl<K: Ord + Clone, T: CommutativeMonoid + Clone> SparseFenwickTree2D<K, T> {
+    /// `points` are the positions that `add` may be called with.
+    pub fn new(points: &[(K, K)]) -> Self {
+        let xs: CoordComp<K> = points.iter().map(|(x, _)| x.clone()).collect();
+        let n = xs.len();
+        let mut ys = vec![vec![]; n];
+        for (x, y) in points {
+            let mut i = xs.lower_bound(x) + 1;
+            while i <= n {
+                ys[i - 1].push(y.clone());
+                i += i & (!i + 1);
+            }
+        }
+        let ys: Vec<CoordComp<K>> = ys.into_iter().map(CoordComp::from).collect();
+        let bits = ys.iter().map(|ys| FenwickTree::new(ys.len())).collect();
+        Self { xs, ys, bits }
+    }
+
+    /// Builds the tree and adds `v` at each `(x, y)`.
+    pub fn from_weighted(points: Vec<(K, K, T)>) -> Self {
+        let keys: Vec<(K, K)> = points
+            .iter()
+            .map(|(x, y, _)| (x.clone(), y.clone()))
+            .collect();
+        let mut bit = Self::new(&keys);
+        for (x, y, v) in points {
+            bit.add(&x, &y, v);
+        }
+        bit
+    }
+
+    /// Panics if `(x, y)` was not given to the constructor.
+    pub fn add(&mut self, x: &K, y: &K, v: T) {
+        let mut i = self.xs.position(x).expect("unknown point") + 1;
+        while i <= self.xs.len() {
+            let j = self.ys[i - 1].position(y).expect("unknown point");
+            self.bits[i - 1].add(j, v.clone());
+            i += i & (!i + 1);
+        }
+    }
+
+    /// Sum over points with `px < x` and `py < y`.
+    pub fn sum(&self, x: &K, y: &K) -> T {
+        let mut s = T::id();
+        let mut i = self.xs.lower_bound(x);
+        while i > 0 {
+            let j = self.ys[i - 1].lower_bound(y);
+            s = self.bits[i - 1].sum(j).op(&s);
+            i = i & i - 1;
+        }
+        s
+    }
+
+    /// Sum over points in `[x0, x1) x [y0, y1)`.
+    pub fn sum_rect(&self, x0: &K, x1: &K, y0: &K, y1: &K) -> T
+    where
+        T: Group,
+    {
+        self.sum(x1, y1)
+            .op(&self.sum(x0, y0))
+            .op_inv(&self.sum(x0, y1).op(&self.sum(x1, y0)))
+    }
 }
@@ -1,177 +1,295 @@
-use std::{iter, ops, ptr, slice};
+use std::{iter, ops::Index, ops::Range, slice};
 
-pub trait Graph<T>
-where
-    for<'a> Self: GraphRef<'a, T>,
-{
+use crate::graph2::{self, WeightedGraph};
+
+/// Adjacency lists packed into one array. `items[start[u]..start[u + 1]]`
+/// are the entries of `u`, in the order they were added.
+#[derive(Clone, Debug)]
+struct Csr<E> {
+    start: Vec<usize>,
+    items: Vec<E>,
 }
 
-pub trait GraphRef<'a, T> {
-    type Adj: Iterator<Item = usize>;
+impl<E> Csr<E> {
+    fn new(n: usize, edges: Vec<(usize, E)>) -> Self {
+        let mut start = vec![0; n + 1];
+        for &(u, _) in &edges {
+            start[u + 1] += 1;
+        }
+        for u in 0..n {
+            start[u + 1] += start[u];
+        }
+        // Place from the back so that each list keeps the insertion order.
+        let mut pos = start[1..].to_vec();
+        let mut items: Vec<Option<E>> = (0..edges.len()).map(|_| None).collect();
+        for (u, e) in edges.into_iter().rev() {
+            pos[u] -= 1;
+            items[pos[u]] = Some(e);
+        }
+        Self {
+            start,
+            items: items.into_iter().map(Option::unwrap).collect(),
+        }
+    }
 
-    fn adj(&'a self, v: usize) -> Self::Adj;
+    fn len(&self) -> usize {
+        self.start.len() - 1
+    }
+}
 
-    fn num_verteces(&self) -> usize;
+impl<E> Index<usize> for Csr<E> {
+    type Output = [E];
 
-    fn num_edges(&self) -> usize;
+    fn index(&self, u: usize) -> &[E] {
+        &self.items[self.start[u]..self.start[u + 1]]
+    }
 }
 
-trait LabeledGraphRef<'a, T: 'a> {
-    type LabeledAdj: Iterator<Item = (usize, &'a T)>;
+/// Directed graph in CSR form. `g[u]` is the slice of the successors of `u`.
+#[derive(Clone, Debug)]
+pub struct Graph(Csr<usize>);
 
-    fn labeled_adj(&'a self) -> Self::LabeledAdj;
-}
+/// Directed graph with a label of type `T` on each edge. `g[u]` is the slice
+/// of `(v, label)` for the edges out of `u`.
+#[derive(Clone, Debug)]
+pub struct LabeledGraph<T>(Csr<(usize, T)>);
 
-#[derive(Clone, Default)]
-pub struct AdjList<T> {
-    indeces: Vec<usize>,
-    edges: Vec<usize>,
-    labels: Vec<T>,
+/// Collects edges and packs them into a `Graph` or a `LabeledGraph`.
+#[derive(Clone, Debug)]
+pub struct GraphBuilder<T = ()> {
+    num_nodes: usize,
+    edges: Vec<(usize, (usize, T))>,
 }
 
-impl<T> AdjList<T> {
-    pub fn len(&self) -> usize {
-        self.indeces.len() - 1
+impl<T> GraphBuilder<T> {
+    pub fn new(num_nodes: usize) -> Self {
+        Self::with_capacity(num_nodes, 0)
     }
-}
 
-impl<T> ops::Index<usize> for AdjList<T> {
-    type Output = [usize];
+    pub fn with_capacity(num_nodes: usize, num_edges: usize) -> Self {
+        Self {
+            num_nodes,
+            edges: Vec::with_capacity(num_edges),
+        }
+    }
 
-    fn index(&self, i: usize) -> &Self::Output {
-        &self.edges[self.indeces[i]..self.indeces[i + 1]]
+    pub fn num_nodes(&self) -> usize {
+        self.num_nodes
     }
-}
 
-impl<'a, T> GraphRef<'a, T> for AdjList<T> {
-    type Adj = Adj<'a>;
+    pub fn num_edges(&self) -> usize {
+        self.edges.len()
+    }
 
-    fn adj(&'a self, v: usize) -> Self::Adj {
-        Adj(self[v].iter())
+    pub fn add_labeled_edge(&mut self, u: usize, v: usize, label: T) -> &mut Self {
+        assert!(u < self.num_nodes && v < self.num_nodes);
+        self.edges.push((u, (v, label)));
+        self
     }
 
-    fn num_verteces(&self) -> usize {
-        self.len()
+    /// Adds both `u -> v` and `v -> u`. A self-loop is added twice.
+    pub fn add_labeled_undirected_edge(&mut self, u: usize, v: usize, label: T) -> &mut Self
+    where
+        T: Clone,
+    {
+        self.add_labeled_edge(u, v, label.clone())
+            .add_labeled_edge(v, u, label)
     }
 
-    fn num_edges(&self) -> usize {
-        self.edges.len()
+    pub fn build_labeled(self) -> LabeledGraph<T> {
+        LabeledGraph(Csr::new(self.num_nodes, self.edges))
     }
 }
 
-#[derive(Clone, Debug)]
-pub struct Adj<'a>(std::slice::Iter<'a, usize>);
-
-impl<'a> Iterator for Adj<'a> {
-    type Item = usize;
+impl GraphBuilder {
+    pub fn add_edge(&mut self, u: usize, v: usize) -> &mut Self {
+        self.add_labeled_edge(u, v, ())
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        self.0.next().copied()
+    pub fn add_undirected_edge(&mut self, u: usize, v: usize) -> &mut Self {
+        self.add_labeled_undirected_edge(u, v, ())
     }
 
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        self.0.size_hint()
+    pub fn build(self) -> Graph {
+        let edges = self.edges.into_iter().map(|(u, (v, ()))| (u, v)).collect();
+        Graph(Csr::new(self.num_nodes, edges))
     }
 }
 
-impl<'a> DoubleEndedIterator for Adj<'a> {
-    fn next_back(&mut self) -> Option<Self::Item> {
-        self.0.next_back().copied()
+impl Graph {
+    pub fn from_edges(num_nodes: usize, edges: impl IntoIterator<Item = (usize, usize)>) -> Self {
+        let mut b = GraphBuilder::new(num_nodes);
+        for (u, v) in edges {
+            b.add_edge(u, v);
+        }
+        b.build()
     }
-}
 
-impl<'a> ExactSizeIterator for Adj<'a> {}
+    pub fn from_undirected_edges(
+        num_nodes: usize,
+        edges: impl IntoIterator<Item = (usize, usize)>,
+    ) -> Self {
+        let mut b = GraphBuilder::new(num_nodes);
+        for (u, v) in edges {
+            b.add_undirected_edge(u, v);
+        }
+        b.build()
+    }
 
-pub struct LabeledAdj<'a, T> {
-    edges: &'a [usize],
-    labels: &'a [T],
-}
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
 
-pub struct AdjListBuilder<T> {
-    heads: Vec<usize>,
-    edges: Vec<(usize, usize)>,
-    labels: Vec<T>,
-}
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 
-const NIL: usize = !0;
+    pub fn num_edges(&self) -> usize {
+        self.0.items.len()
+    }
 
-impl AdjListBuilder<()> {
-    pub fn add_edge(&mut self, u: usize, v: usize) {
-        self.add_labeled_edge(u, v, ());
+    /// Graph with every edge reversed.
+    pub fn reversed(&self) -> Self {
+        let edges = (0..self.len())
+            .flat_map(|u| self[u].iter().map(move |&v| (v, u)))
+            .collect();
+        Self(Csr::new(self.len(), edges))
     }
 
-    pub fn add_bi_edge(&mut self, u: usize, v: usize) {
-        self.add_labeled_bi_edge(u, v, ());
+    /// Graph with every edge in both directions.
+    pub fn undirected(&self) -> Self {
+        let edges = (0..self.len())
+            .flat_map(|u| self[u].iter().flat_map(move |&v| [(u, v), (v, u)]))
+            .collect();
+        Self(Csr::new(self.len(), edges))
     }
 }
 
-impl<T> AdjListBuilder<T> {
-    pub fn new(num_verteces: usize) -> Self {
-        Self::with_capacity(num_verteces, 0)
+impl<T> LabeledGraph<T> {
+    pub fn from_edges(
+        num_nodes: usize,
+        edges: impl IntoIterator<Item = (usize, usize, T)>,
+    ) -> Self {
+        let mut b = GraphBuilder::new(num_nodes);
+        for (u, v, label) in edges {
+            b.add_labeled_edge(u, v, label);
+        }
+        b.build_labeled()
     }
 
-    pub fn with_capacity(num_verteces: usize, cap_edges: usize) -> Self {
-        let mut heads = Vec::with_capacity(num_verteces + 1);
-        heads.resize(num_verteces, NIL);
-        Self {
-            heads,
-            edges: Vec::with_capacity(cap_edges),
-            labels: Vec::with_capacity(cap_edges),
+    pub fn from_undirected_edges(
+        num_nodes: usize,
+        edges: impl IntoIterator<Item = (usize, usize, T)>,
+    ) -> Self
+    where
+        T: Clone,
+    {
+        let mut b = GraphBuilder::new(num_nodes);
+        for (u, v, label) in edges {
+            b.add_labeled_undirected_edge(u, v, label);
         }
+        b.build_labeled()
     }
 
-    pub fn num_verteces(&self) -> usize {
-        self.heads.len()
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
     }
 
     pub fn num_edges(&self) -> usize {
-        self.edges.len()
+        self.0.items.len()
     }
 
-    pub fn add_labeled_edge(&mut self, u: usize, v: usize, label: T) {
-        self.edges.push((v, self.heads[u]));
-        self.heads[u] = self.edges.len() - 1;
-        self.labels.push(label);
+    /// The graph without the labels.
+    pub fn unlabeled(&self) -> Graph {
+        Graph(Csr {
+            start: self.0.start.clone(),
+            items: self.0.items.iter().map(|&(v, _)| v).collect(),
+        })
     }
 
-    pub fn add_labeled_bi_edge(&mut self, u: usize, v: usize, label: T)
+    pub fn reversed(&self) -> Self
     where
         T: Clone,
     {
-        self.add_labeled_edge(u, v, label.clone());
-        self.add_labeled_edge(v, u, label);
-    }
-
-    pub fn build(mut self) -> AdjList<T> {
-        let mut edges = Vec::<usize>::with_capacity(self.edges.len());
-        let edges_ptr = edges.as_mut_ptr();
-        let mut labels = Vec::<T>::with_capacity(self.labels.len());
-        let labels_ptr = labels.as_mut_ptr();
-        let mut rest = self.num_edges();
-        for u in (0..self.num_verteces()).rev() {
-            let mut i = self.heads[u];
-            while let Some(&(v, next_i)) = self.edges.get(i) {
-                unsafe {
-                    rest -= 1;
-                    *edges_ptr.add(rest) = v;
-                    ptr::copy_nonoverlapping(self.labels.get_unchecked(i), labels_ptr.add(rest), 1);
-                }
-                i = next_i;
-            }
-            self.heads[u] = i;
-        }
-        self.heads.push(self.num_edges());
-        debug_assert_eq!(rest, 0);
-        unsafe {
-            let num_edges = self.num_edges();
-            self.labels.set_len(0);
-            edges.set_len(num_edges);
-            labels.set_len(num_edges);
-        }
-        AdjList {
-            indeces: self.heads,
-            edges,
-            labels,
-        }
+        let edges = (0..self.len())
+            .flat_map(|u| self[u].iter().map(move |(v, l)| (*v, (u, l.clone()))))
+            .collect();
+        Self(Csr::new(self.len(), edges))
+    }
+
+    pub fn undirected(&self) -> Self
+    where
+        T: Clone,
+    {
+        let edges = (0..self.len())
+            .flat_map(|u| {
+                self[u]
+                    .iter()
+                    .flat_map(move |(v, l)| [(u, (*v, l.clone())), (*v, (u, l.clone()))])
+            })
+            .collect();
+        Self(Csr::new(self.len(), edges))
+    }
+}
+
+impl Index<usize> for Graph {
+    type Output = [usize];
+
+    fn index(&self, u: usize) -> &[usize] {
+        &self.0[u]
+    }
+}
+
+impl<T> Index<usize> for LabeledGraph<T> {
+    type Output = [(usize, T)];
+
+    fn index(&self, u: usize) -> &[(usize, T)] {
+        &self.0[u]
+    }
+}
+
+impl graph2::Graph for Graph {
+    type Node = usize;
+    type Nodes<'a> = Range<usize>;
+    type Neighbors<'a> = iter::Copied<slice::Iter<'a, usize>>;
+
+    fn nodes(&self) -> Self::Nodes<'_> {
+        0..self.len()
+    }
+
+    fn neighbors(&self, u: usize) -> Self::Neighbors<'_> {
+        self[u].iter().copied()
+    }
+}
+
+impl<T> graph2::Graph for LabeledGraph<T> {
+    type Node = usize;
+    type Nodes<'a> = Range<usize>
+    where
+        T: 'a;
+    type Neighbors<'a> = iter::Map<slice::Iter<'a, (usize, T)>, fn(&'a (usize, T)) -> usize>
+    where
+        T: 'a;
+
+    fn nodes(&self) -> Self::Nodes<'_> {
+        0..self.len()
+    }
+
+    fn neighbors(&self, u: usize) -> Self::Neighbors<'_> {
+        self[u].iter().map(|&(v, _)| v)
+    }
+}
+
+impl<T: Clone> WeightedGraph<T> for LabeledGraph<T> {
+    type Edges<'a> = iter::Cloned<slice::Iter<'a, (usize, T)>>
+    where
+        T: 'a;
+
+    fn edges(&self, u: usize) -> Self::Edges<'_> {
+        self[u].iter().cloned()
     }
 }
@@ -1,5 +1,7 @@
 use std::{collections::VecDeque, iter, ops::Range, slice};
 
+use crate::tsort;
+
 pub trait Graph {
     type Node: Copy;
     type Nodes<'a>: Iterator<Item = Self::Node> + ExactSizeIterator
@@ -23,12 +25,21 @@ pub trait IndexedGraph: Graph<Node = usize> {
     }
 
     fn tsort(&self) -> Option<Vec<usize>> {
-        tsort(self)
+        tsort::tsort(self)
     }
 }
 
 impl<T: Graph<Node = usize> + ?Sized> IndexedGraph for T {}
 
+/// Graph whose adjacency lists carry edge weights.
+pub trait WeightedGraph<W>: IndexedGraph {
+    type Edges<'a>: Iterator<Item = (usize, W)>
+    where
+        Self: 'a;
+
+    fn edges(&self, u: usize) -> Self::Edges<'_>;
+}
+
 type Neighbors<'a> = iter::Copied<slice::Iter<'a, usize>>;
 
 impl Graph for [Vec<usize>] {
@@ -49,6 +60,20 @@ impl Graph for [Vec<usize>] {
     }
 }
 
+impl Graph for Vec<Vec<usize>> {
+    type Node = usize;
+    type Nodes<'a> = Range<usize>;
+    type Neighbors<'a> = Neighbors<'a>;
+
+    fn nodes(&self) -> Self::Nodes<'_> {
+        0..self.len()
+    }
+
+    fn neighbors(&self, u: Self::Node) -> Self::Neighbors<'_> {
+        self[u].iter().copied()
+    }
+}
+
 impl<T> Graph for [Vec<(usize, T)>] {
     type Node = usize;
     type Nodes<'a> = Range<usize>
@@ -67,6 +92,16 @@ impl<T> Graph for [Vec<(usize, T)>] {
     }
 }
 
+impl<W: Clone> WeightedGraph<W> for [Vec<(usize, W)>] {
+    type Edges<'a> = iter::Cloned<slice::Iter<'a, (usize, W)>>
+    where
+        Self: 'a;
+
+    fn edges(&self, u: usize) -> Self::Edges<'_> {
+        self[u].iter().cloned()
+    }
+}
+
 fn dist_bfs<G: IndexedGraph + ?Sized>(g: &G, u: usize) -> Vec<usize> {
     let mut que = VecDeque::new();
     que.push_back(u);
@@ -82,55 +117,3 @@ fn dist_bfs<G: IndexedGraph + ?Sized>(g: &G, u: usize) -> Vec<usize> {
     }
     dist
 }
-
-const fn ceil_div(x: usize, y: usize) -> usize {
-    x / y + (x % y != 0) as usize
-}
-
-fn tsort<G: IndexedGraph + ?Sized>(g: &G) -> Option<Vec<usize>> {
-    fn dfs_neighbors<G: IndexedGraph + ?Sized>(
-        g: &G,
-        res: &mut Vec<usize>,
-        states: &mut [usize],
-        u: usize,
-    ) -> bool {
-        for v in g.neighbors(u) {
-            if !visit(g, res, states, v) {
-                return false;
-            }
-        }
-        res.push(u);
-        true
-    }
-
-    #[inline]
-    fn visit<G: IndexedGraph + ?Sized>(
-        g: &G,
-        res: &mut Vec<usize>,
-        states: &mut [usize],
-        v: usize,
-    ) -> bool {
-        let i = 2 * v / usize::BITS as usize;
-        let j = 2 * v % usize::BITS as usize;
-        let state = (states[i] >> j) & 3;
-        if state & 1 != 0 {
-            return state & 2 != 0;
-        }
-        states[i] |= 1 << j;
-        if !dfs_neighbors(g, res, states, v) {
-            return false;
-        }
-        states[i] |= 1 << (j + 1);
-        true
-    }
-
-    let mut res = Vec::with_capacity(g.num_nodes());
-    let mut states = vec![0usize; ceil_div(2 * g.num_nodes(), usize::BITS as usize)];
-    for s in g.nodes() {
-        if !visit(g, &mut res, &mut states, s) {
-            return None;
-        }
-    }
-    res.reverse();
-    Some(res)
-}
@@ -1 +1,179 @@
+use std::ops::Range;
 
+use crate::{algebra::Monoid, graph2::IndexedGraph, lca::Tree};
+
+const NIL: usize = !0;
+
+/// Heavy-light decomposition.
+///
+/// Vertices are laid out so that every heavy path and every subtree is a
+/// contiguous range of positions, so the ranges can be passed directly to
+/// `SegTree::sum` or `LazySegTree::prod`.
+#[derive(Clone, Debug)]
+pub struct Hld {
+    par: Vec<usize>,
+    depth: Vec<usize>,
+    size: Vec<usize>,
+    head: Vec<usize>,
+    pos: Vec<usize>,
+    ord: Vec<usize>,
+}
+
+impl Hld {
+    /// `par[root]` must be `root` itself or `!0`.
+    pub fn from_parents(par: &[usize]) -> Self {
+        Self::build(Tree::from_parents(par))
+    }
+
+    pub fn new<G: IndexedGraph + ?Sized>(g: &G, root: usize) -> Self {
+        Self::build(Tree::from_graph(g, root))
+    }
+
+    fn build(tree: Tree) -> Self {
+        let n = tree.par.len();
+        let bfs = tree.bfs();
+        let mut size = vec![1; n];
+        for &v in bfs[1.min(n)..].iter().rev() {
+            size[tree.par[v]] += size[v];
+        }
+        let heavy: Vec<usize> = (0..n)
+            .map(|u| {
+                tree.children(u)
+                    .iter()
+                    .copied()
+                    .max_by_key(|&c| size[c])
+                    .unwrap_or(NIL)
+            })
+            .collect();
+        let mut head = vec![NIL; n];
+        let mut pos = vec![NIL; n];
+        let mut ord = Vec::with_capacity(n);
+        let mut stack = bfs[..1.min(n)].to_vec();
+        while let Some(h) = stack.pop() {
+            let mut u = h;
+            while u != NIL {
+                head[u] = h;
+                pos[u] = ord.len();
+                ord.push(u);
+                stack.extend(tree.children(u).iter().filter(|&&c| c != heavy[u]));
+                u = heavy[u];
+            }
+        }
+        Self {
+            par: tree.par,
+            depth: tree.depth,
+            size,
+            head,
+            pos,
+            ord,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.par.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.par.is_empty()
+    }
+
+    pub fn root(&self) -> usize {
+        self.ord[0]
+    }
+
+    pub fn parent(&self, v: usize) -> Option<usize> {
+        Some(self.par[v]).filter(|&p| p != NIL)
+    }
+
+    pub fn depth(&self, v: usize) -> usize {
+        self.depth[v]
+    }
+
+    pub fn size(&self, v: usize) -> usize {
+        self.size[v]
+    }
+
+    /// Position of `v` in the decomposed order.
+    pub fn pos(&self, v: usize) -> usize {
+        self.pos[v]
+    }
+
+    /// Vertices in the decomposed order, i.e. `order()[pos(v)] == v`.
+    pub fn order(&self) -> &[usize] {
+        &self.ord
+    }
+
+    pub fn subtree(&self, v: usize) -> Range<usize> {
+        self.pos[v]..self.pos[v] + self.size[v]
+    }
+
+    pub fn lca(&self, mut u: usize, mut v: usize) -> usize {
+        while self.head[u] != self.head[v] {
+            if self.depth[self.head[u]] > self.depth[self.head[v]] {
+                u = self.par[self.head[u]];
+            } else {
+                v = self.par[self.head[v]];
+            }
+        }
+        if self.depth[u] <= self.depth[v] {
+            u
+        } else {
+            v
+        }
+    }
+
+    pub fn dist(&self, u: usize, v: usize) -> usize {
+        self.depth[u] + self.depth[v] - 2 * self.depth[self.lca(u, v)]
+    }
+
+    pub fn kth_ancestor(&self, mut v: usize, k: usize) -> Option<usize> {
+        let d = self.depth[v].checked_sub(k)?;
+        while self.depth[self.head[v]] > d {
+            v = self.par[self.head[v]];
+        }
+        Some(self.ord[self.pos[v] - (self.depth[v] - d)])
+    }
+
+    /// Ranges covering the path from `u` to `v`, in order from `u` to `v`.
+    ///
+    /// A range flagged `true` is traversed towards the root, i.e. in
+    /// decreasing position order. If `edge` is set the LCA is excluded, which
+    /// is what edge weights stored at the child vertex need.
+    pub fn path(&self, mut u: usize, mut v: usize, edge: bool) -> Vec<(Range<usize>, bool)> {
+        let mut up = vec![];
+        let mut down = vec![];
+        while self.head[u] != self.head[v] {
+            if self.depth[self.head[u]] >= self.depth[self.head[v]] {
+                up.push((self.pos[self.head[u]]..self.pos[u] + 1, true));
+                u = self.par[self.head[u]];
+            } else {
+                down.push((self.pos[self.head[v]]..self.pos[v] + 1, false));
+                v = self.par[self.head[v]];
+            }
+        }
+        if self.depth[u] >= self.depth[v] {
+            up.push((self.pos[v] + edge as usize..self.pos[u] + 1, true));
+        } else {
+            down.push((self.pos[u] + edge as usize..self.pos[v] + 1, false));
+        }
+        up.extend(down.into_iter().rev());
+        up.retain(|(r, _)| !r.is_empty());
+        up
+    }
+
+    /// Folds the path from `u` to `v` in order.
+    ///
+    /// `f(range, rev)` must return the product over `range`, taken in
+    /// decreasing position order when `rev` is set.
+    pub fn fold_path<T: Monoid>(
+        &self,
+        u: usize,
+        v: usize,
+        edge: bool,
+        mut f: impl FnMut(Range<usize>, bool) -> T,
+    ) -> T {
+        self.path(u, v, edge)
+            .into_iter()
+            .fold(T::id(), |acc, (r, rev)| acc.op(&f(r, rev)))
+    }
+}
@@ -63,7 +63,7 @@ pub fn crt<T: Int>(r1: T, m1: T, r2: T, m2: T) -> Option<(T, T)> {
     }
     let (g, v1, _) = m1.ext_gcd(m2);
     if m1 == g {
-        return if r1 == r2 % m1 { Some((r1, m1)) } else { None };
+        return if r1 == r2 % m1 { Some((r2, m2)) } else { None };
     }
     if (r2 - r1) % g != T::ZERO {
         return None;
@@ -77,10 +77,100 @@ pub fn crt<T: Int>(r1: T, m1: T, r2: T, m2: T) -> Option<(T, T)> {
     Some((x, m1 * u2))
 }
 
+/// Solves `x = r_i (mod m_i)` for all congruences, where the moduli need not
+/// be coprime. Returns `(x, lcm)` with `0 <= x < lcm`, or `None` if the system
+/// is inconsistent. The lcm must fit in `i64`.
+pub fn crt_many(congruences: impl IntoIterator<Item = (i64, i64)>) -> Option<(i64, i64)> {
+    let (mut r, mut m) = (0i128, 1i128);
+    for (r2, m2) in congruences {
+        assert!(m2 > 0);
+        let m2 = m2 as i128;
+        (r, m) = crt(r, m, (r2 as i128).rem_euclid(m2), m2)?;
+        assert!(m <= i64::MAX as i128, "lcm overflows i64");
+    }
+    Some((r as i64, m as i64))
+}
+
+/// `x mod m` for the unique `0 <= x < prod(mods)` with `x = rems[i] (mod
+/// mods[i])`, by Garner's algorithm. `mods` must be pairwise coprime.
+pub fn garner(rems: &[u64], mods: &[u64], m: u64) -> u64 {
+    assert_eq!(rems.len(), mods.len());
+    let mut inv = vec![0; mods.len()];
+    garner_inverses(mods, &mut inv);
+    let mut t = vec![0; mods.len()];
+    garner_digits(rems, mods, &inv, &mut t);
+    mixed_radix_mod(&t, mods, m)
+}
+
+/// Garner's algorithm for fixed pairwise coprime moduli, with the inverses
+/// computed once.
+#[derive(Clone, Copy, Debug)]
+pub struct Garner<const N: usize> {
+    mods: [u64; N],
+    inv: [u64; N],
+}
+
+impl<const N: usize> Garner<N> {
+    pub fn new(mods: [u64; N]) -> Self {
+        let mut inv = [0; N];
+        garner_inverses(&mods, &mut inv);
+        Self { mods, inv }
+    }
+
+    /// Digits `t` with `x = t[0] + t[1] mods[0] + t[2] mods[0] mods[1] + ...`
+    /// and `t[i] < mods[i]`.
+    pub fn digits(&self, rems: [u64; N]) -> [u64; N] {
+        let mut t = [0; N];
+        garner_digits(&rems, &self.mods, &self.inv, &mut t);
+        t
+    }
+
+    /// Same as `garner(&rems, &mods, m)`.
+    pub fn solve(&self, rems: [u64; N], m: u64) -> u64 {
+        mixed_radix_mod(&self.digits(rems), &self.mods, m)
+    }
+}
+
+#[inline]
+fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    (a as u128 * b as u128 % m as u128) as u64
+}
+
+/// `inv[i]` is the inverse of `mods[0] ... mods[i - 1]` modulo `mods[i]`.
+fn garner_inverses(mods: &[u64], inv: &mut [u64]) {
+    for i in 0..mods.len() {
+        let mi = mods[i];
+        let c = mods[..i].iter().fold(1 % mi, |c, &mj| mul_mod(c, mj, mi));
+        let (_, a, _) = ext_gcd(c as i128, mi as i128);
+        inv[i] = a.rem_euclid(mi as i128) as u64;
+    }
+}
+
+fn garner_digits(rems: &[u64], mods: &[u64], inv: &[u64], t: &mut [u64]) {
+    for i in 0..mods.len() {
+        let mi = mods[i];
+        let x = mixed_radix_mod(&t[..i], mods, mi);
+        let r = rems[i] % mi;
+        let d = if r >= x { r - x } else { r + (mi - x) };
+        t[i] = mul_mod(d, inv[i], mi);
+    }
+}
+
+/// `(t[0] + t[1] mods[0] + t[2] mods[0] mods[1] + ...) mod m`.
+fn mixed_radix_mod(t: &[u64], mods: &[u64], m: u64) -> u64 {
+    let mut x = 0;
+    let mut c = 1 % m;
+    for (&ti, &mi) in t.iter().zip(mods) {
+        x = ((x as u128 + mul_mod(ti % m, c, m) as u128) % m as u128) as u64;
+        c = mul_mod(c, mi % m, m);
+    }
+    x
+}
+
 macro_rules! common_fns {
     ($ty:ty) => {
         const ZERO: Self = 0;
-        const ONE: Self = 0;
+        const ONE: Self = 1;
         fn gcd(self, other: Self) -> Self {
             let x = self.abs();
             let y = other.abs();
@@ -1,45 +1,49 @@
-pub trait Monoid {
-    fn id() -> Self;
-    fn op(&self, other: &Self) -> Self;
-}
+pub use crate::algebra::{Action, Monoid};
 
-pub trait Map<T> {
-    fn map(&self, x: T) -> T;
-}
 pub struct LazySegTree<T, F> {
+    n: usize,
     ss: Box<[T]>,
     fs: Box<[F]>,
 }
-impl<T: Monoid, F: Monoid + Map<T>> LazySegTree<T, F> {
+impl<T: Monoid, F: Action<T>> LazySegTree<T, F> {
     pub fn new(n: usize) -> Self {
         use std::iter::repeat_with;
         let len = 2 * n.next_power_of_two();
         Self {
+            n,
             ss: repeat_with(T::id).take(len).collect(),
             fs: repeat_with(F::id).take(len).collect(),
         }
     }
-    fn len(&self) -> usize {
+    pub fn len(&self) -> usize {
+        self.n
+    }
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+    fn size(&self) -> usize {
         self.ss.len() / 2
     }
+    fn push(&mut self, p: usize) {
+        let l = 2 * p;
+        let r = 2 * p + 1;
+        self.ss[l] = self.fs[p].act(&self.ss[l]);
+        self.ss[r] = self.fs[p].act(&self.ss[r]);
+        self.fs[l] = self.fs[p].op(&self.fs[l]);
+        self.fs[r] = self.fs[p].op(&self.fs[r]);
+        self.fs[p] = F::id();
+    }
     fn propagate(&mut self, i: usize) {
         let h = 8 * std::mem::size_of::<usize>() as u32 - i.leading_zeros();
         for k in (1..h).rev() {
-            let p = i >> k;
-            let l = 2 * p;
-            let r = 2 * p + 1;
-            self.ss[l] = self.fs[p].map(std::mem::replace(&mut self.ss[l], T::id()));
-            self.ss[r] = self.fs[p].map(std::mem::replace(&mut self.ss[r], T::id()));
-            self.fs[l] = self.fs[p].op(&self.fs[l]);
-            self.fs[r] = self.fs[p].op(&self.fs[r]);
-            self.fs[p] = F::id();
+            self.push(i >> k);
         }
     }
     pub fn prod(&mut self, l: usize, r: usize) -> T {
         assert!(l <= r);
         assert!(r <= self.len());
-        let mut l = l + self.len();
-        let mut r = r + self.len();
+        let mut l = l + self.size();
+        let mut r = r + self.size();
         self.propagate(l >> l.trailing_zeros());
         self.propagate((r >> r.trailing_zeros()) - 1);
         let mut lv = T::id();
@@ -59,7 +63,7 @@ impl<T: Monoid, F: Monoid + Map<T>> LazySegTree<T, F> {
         lv.op(&rv)
     }
     pub fn set(&mut self, i: usize, v: T) {
-        let mut i = i + self.len();
+        let mut i = i + self.size();
         self.propagate(i);
         self.ss[i] = v;
         while i > 1 {
@@ -70,8 +74,8 @@ impl<T: Monoid, F: Monoid + Map<T>> LazySegTree<T, F> {
     pub fn apply(&mut self, l: usize, r: usize, f: &F) {
         assert!(l <= r);
         assert!(r <= self.len());
-        let mut li = l + self.len();
-        let mut ri = r + self.len();
+        let mut li = l + self.size();
+        let mut ri = r + self.size();
         let ln = li >> li.trailing_zeros();
         let rn = ri >> ri.trailing_zeros();
         self.propagate(ln);
@@ -79,19 +83,19 @@ impl<T: Monoid, F: Monoid + Map<T>> LazySegTree<T, F> {
         while li < ri {
             if li % 2 == 1 {
                 self.fs[li] = f.op(&self.fs[li]);
-                self.ss[li] = f.map(std::mem::replace(&mut self.ss[li], T::id()));
+                self.ss[li] = f.act(&self.ss[li]);
                 li += 1;
             }
             if ri % 2 == 1 {
                 ri -= 1;
                 self.fs[ri] = f.op(&self.fs[ri]);
-                self.ss[ri] = f.map(std::mem::replace(&mut self.ss[ri], T::id()));
+                self.ss[ri] = f.act(&self.ss[ri]);
             }
             li /= 2;
             ri /= 2;
         }
-        let mut l = (l + self.len()) / 2;
-        let mut r = (r + self.len() - 1) / 2;
+        let mut l = (l + self.size()) / 2;
+        let mut r = (r + self.size() - 1) / 2;
         while l > 0 {
             if l < ln {
                 self.ss[l] = self.ss[2 * l].op(&self.ss[2 * l + 1]);
@@ -105,9 +109,77 @@ impl<T: Monoid, F: Monoid + Map<T>> LazySegTree<T, F> {
             r /= 2;
         }
     }
+    /// Largest `r` such that `pred(prod(l, r))` holds, assuming `pred` is
+    /// monotone and `pred(T::id())`.
+    pub fn max_right<P: FnMut(&T) -> bool>(&mut self, l: usize, mut pred: P) -> usize {
+        assert!(l <= self.len());
+        debug_assert!(pred(&T::id()));
+        if l == self.len() {
+            return l;
+        }
+        let mut l = l + self.size();
+        self.propagate(l);
+        let mut sum = T::id();
+        loop {
+            l >>= l.trailing_zeros();
+            let x = sum.op(&self.ss[l]);
+            if !pred(&x) {
+                while l < self.size() {
+                    self.push(l);
+                    l *= 2;
+                    let x = sum.op(&self.ss[l]);
+                    if pred(&x) {
+                        sum = x;
+                        l += 1;
+                    }
+                }
+                return (l - self.size()).min(self.len());
+            }
+            sum = x;
+            l += 1;
+            if l.is_power_of_two() {
+                return self.len();
+            }
+        }
+    }
+    /// Smallest `l` such that `pred(prod(l, r))` holds, assuming `pred` is
+    /// monotone and `pred(T::id())`.
+    pub fn min_left<P: FnMut(&T) -> bool>(&mut self, r: usize, mut pred: P) -> usize {
+        assert!(r <= self.len());
+        debug_assert!(pred(&T::id()));
+        if r == 0 {
+            return 0;
+        }
+        let mut r = r + self.size();
+        self.propagate(r - 1);
+        let mut sum = T::id();
+        loop {
+            r -= 1;
+            while r > 1 && r % 2 == 1 {
+                r /= 2;
+            }
+            let x = self.ss[r].op(&sum);
+            if !pred(&x) {
+                while r < self.size() {
+                    self.push(r);
+                    r = 2 * r + 1;
+                    let x = self.ss[r].op(&sum);
+                    if pred(&x) {
+                        sum = x;
+                        r -= 1;
+                    }
+                }
+                return r + 1 - self.size();
+            }
+            sum = x;
+            if r.is_power_of_two() {
+                return 0;
+            }
+        }
+    }
 }
 
-impl<T: Monoid, F: Monoid + Map<T>> std::iter::FromIterator<T> for LazySegTree<T, F> {
+impl<T: Monoid, F: Action<T>> std::iter::FromIterator<T> for LazySegTree<T, F> {
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
         let mut ss: Vec<_> = iter.into_iter().collect();
         let iter_n = ss.len();
@@ -119,6 +191,7 @@ impl<T: Monoid, F: Monoid + Map<T>> std::iter::FromIterator<T> for LazySegTree<T
             ss[i] = ss[2 * i].op(&ss[2 * i + 1]);
         }
         Self {
+            n: iter_n,
             ss: ss.into(),
             fs: std::iter::repeat_with(F::id).take(2 * n).collect(),
         }
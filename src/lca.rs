@@ -0,0 +1,442 @@
+use crate::{
+    algebra::{CommutativeMonoid, Monoid},
+    dsu::Dsu,
+    graph2::IndexedGraph,
+};
+
+const NIL: usize = !0;
+
+/// Rooted tree given by a parent array, with the children in CSR form.
+/// `par[root]` is `!0`.
+pub(crate) struct Tree {
+    pub(crate) par: Vec<usize>,
+    pub(crate) depth: Vec<usize>,
+    start: Vec<usize>,
+    children: Vec<usize>,
+    pub(crate) root: usize,
+}
+
+impl Tree {
+    /// Panics unless `par` is a tree rooted at the only `v` with `par[v]`
+    /// equal to `v` or `!0`.
+    pub(crate) fn from_parents(par: &[usize]) -> Self {
+        let n = par.len();
+        let mut root = NIL;
+        let mut start = vec![0; n + 1];
+        for (v, &p) in par.iter().enumerate() {
+            if p == v || p == NIL {
+                assert_eq!(root, NIL, "multiple roots");
+                root = v;
+            } else {
+                start[p + 1] += 1;
+            }
+        }
+        assert!(n == 0 || root != NIL, "no root");
+        for i in 0..n {
+            start[i + 1] += start[i];
+        }
+        let mut children = vec![0; n.saturating_sub(1)];
+        let mut fill = start.clone();
+        for (v, &p) in par.iter().enumerate() {
+            if v != root {
+                children[fill[p]] = v;
+                fill[p] += 1;
+            }
+        }
+        let par: Vec<usize> = par
+            .iter()
+            .enumerate()
+            .map(|(v, &p)| if v == root { NIL } else { p })
+            .collect();
+        let mut tree = Self {
+            par,
+            depth: vec![0; n],
+            start,
+            children,
+            root,
+        };
+        let bfs = tree.bfs();
+        assert_eq!(bfs.len(), n, "not a tree");
+        for &v in &bfs[1.min(n)..] {
+            tree.depth[v] = tree.depth[tree.par[v]] + 1;
+        }
+        tree
+    }
+
+    pub(crate) fn from_graph<G: IndexedGraph + ?Sized>(g: &G, root: usize) -> Self {
+        let n = g.num_nodes();
+        let mut par = vec![NIL; n];
+        let mut visited = vec![false; n];
+        let mut stack = vec![root];
+        visited[root] = true;
+        while let Some(u) = stack.pop() {
+            for v in g.neighbors(u) {
+                if !visited[v] {
+                    visited[v] = true;
+                    par[v] = u;
+                    stack.push(v);
+                }
+            }
+        }
+        assert!(visited.iter().all(|&b| b), "not connected");
+        Self::from_parents(&par)
+    }
+
+    pub(crate) fn children(&self, u: usize) -> &[usize] {
+        &self.children[self.start[u]..self.start[u + 1]]
+    }
+
+    pub(crate) fn bfs(&self) -> Vec<usize> {
+        let mut bfs = Vec::with_capacity(self.par.len());
+        if !self.par.is_empty() {
+            bfs.push(self.root);
+        }
+        let mut i = 0;
+        while i < bfs.len() {
+            bfs.extend_from_slice(self.children(bfs[i]));
+            i += 1;
+        }
+        bfs
+    }
+
+    fn preorder(&self) -> Vec<usize> {
+        let mut ord = Vec::with_capacity(self.par.len());
+        let mut stack = vec![];
+        if !self.par.is_empty() {
+            stack.push(self.root);
+        }
+        while let Some(u) = stack.pop() {
+            ord.push(u);
+            stack.extend_from_slice(self.children(u));
+        }
+        ord
+    }
+}
+
+/// LCA in O(1) by a sparse table over the DFS order, after O(n log n)
+/// preprocessing. `kth_ancestor` takes O(log n).
+#[derive(Clone, Debug)]
+pub struct Lca {
+    par: Vec<usize>,
+    depth: Vec<usize>,
+    pos: Vec<usize>,
+    /// `table[k][i]`: the parent of `ord[j]` with the smallest position for
+    /// `j` in `i + 1..i + 1 + 2^k`.
+    table: Vec<Vec<usize>>,
+    /// Vertices sorted by depth, then by position.
+    by_depth: Vec<usize>,
+    depth_start: Vec<usize>,
+}
+
+impl Lca {
+    /// `par` as in `Hld::from_parents`.
+    pub fn from_parents(par: &[usize]) -> Self {
+        Self::build(Tree::from_parents(par))
+    }
+
+    pub fn new<G: IndexedGraph + ?Sized>(g: &G, root: usize) -> Self {
+        Self::build(Tree::from_graph(g, root))
+    }
+
+    fn build(tree: Tree) -> Self {
+        let n = tree.par.len();
+        let ord = tree.preorder();
+        let mut pos = vec![0; n];
+        for (i, &v) in ord.iter().enumerate() {
+            pos[v] = i;
+        }
+        let mut table = vec![ord[1.min(n)..]
+            .iter()
+            .map(|&v| tree.par[v])
+            .collect::<Vec<_>>()];
+        let mut k = 1;
+        while 2 * k <= table[0].len() {
+            let prev = table.last().unwrap();
+            let next = (0..prev.len() - k)
+                .map(|i| {
+                    let (a, b) = (prev[i], prev[i + k]);
+                    if pos[a] <= pos[b] {
+                        a
+                    } else {
+                        b
+                    }
+                })
+                .collect();
+            table.push(next);
+            k *= 2;
+        }
+        let max_depth = tree.depth.iter().copied().max().unwrap_or(0);
+        let mut depth_start = vec![0; max_depth + 2];
+        for &d in &tree.depth {
+            depth_start[d + 1] += 1;
+        }
+        for d in 0..=max_depth {
+            depth_start[d + 1] += depth_start[d];
+        }
+        let mut fill = depth_start.clone();
+        let mut by_depth = vec![0; n];
+        for &v in &ord {
+            by_depth[fill[tree.depth[v]]] = v;
+            fill[tree.depth[v]] += 1;
+        }
+        Self {
+            par: tree.par,
+            depth: tree.depth,
+            pos,
+            table,
+            by_depth,
+            depth_start,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.par.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.par.is_empty()
+    }
+
+    pub fn parent(&self, v: usize) -> Option<usize> {
+        Some(self.par[v]).filter(|&p| p != NIL)
+    }
+
+    pub fn depth(&self, v: usize) -> usize {
+        self.depth[v]
+    }
+
+    pub fn lca(&self, u: usize, v: usize) -> usize {
+        if u == v {
+            return u;
+        }
+        let (l, r) = if self.pos[u] < self.pos[v] {
+            (self.pos[u], self.pos[v])
+        } else {
+            (self.pos[v], self.pos[u])
+        };
+        let k = (r - l).ilog2() as usize;
+        let (a, b) = (self.table[k][l], self.table[k][r - (1 << k)]);
+        if self.pos[a] <= self.pos[b] {
+            a
+        } else {
+            b
+        }
+    }
+
+    pub fn dist(&self, u: usize, v: usize) -> usize {
+        self.depth[u] + self.depth[v] - 2 * self.depth[self.lca(u, v)]
+    }
+
+    pub fn kth_ancestor(&self, v: usize, k: usize) -> Option<usize> {
+        let d = self.depth[v].checked_sub(k)?;
+        let level = &self.by_depth[self.depth_start[d]..self.depth_start[d + 1]];
+        let i = level.partition_point(|&u| self.pos[u] <= self.pos[v]);
+        Some(level[i - 1])
+    }
+
+    /// The `k`-th vertex on the path from `u` to `v`, where `u` itself is the
+    /// 0th.
+    pub fn jump(&self, u: usize, v: usize, k: usize) -> Option<usize> {
+        let w = self.lca(u, v);
+        let du = self.depth[u] - self.depth[w];
+        let dv = self.depth[v] - self.depth[w];
+        if k <= du {
+            self.kth_ancestor(u, k)
+        } else if k <= du + dv {
+            self.kth_ancestor(v, du + dv - k)
+        } else {
+            None
+        }
+    }
+}
+
+/// LCA by binary lifting in O(log n), which also folds a monoid over the
+/// edges on the way up. The weight of the edge between `v` and its parent is
+/// stored at `v`.
+#[derive(Clone, Debug)]
+pub struct DoublingLca<T = ()> {
+    depth: Vec<usize>,
+    /// `anc[k][v]`: the `2^k`-th ancestor of `v`, or the root if there is none.
+    anc: Vec<Vec<usize>>,
+    /// `up[k][v]`: the product of the `2^k` edges above `v`, from `v` upwards.
+    up: Vec<Vec<T>>,
+    root: usize,
+}
+
+impl DoublingLca {
+    pub fn from_parents(par: &[usize]) -> Self {
+        Self::with_weights(par, |_| ())
+    }
+
+    pub fn new<G: IndexedGraph + ?Sized>(g: &G, root: usize) -> Self {
+        Self::build(Tree::from_graph(g, root), |_| ())
+    }
+}
+
+impl<T: Monoid + Clone> DoublingLca<T> {
+    /// `weight(v)` is the weight of the edge from `v` to its parent.
+    pub fn with_weights(par: &[usize], weight: impl FnMut(usize) -> T) -> Self {
+        Self::build(Tree::from_parents(par), weight)
+    }
+
+    fn build(tree: Tree, mut weight: impl FnMut(usize) -> T) -> Self {
+        let n = tree.par.len();
+        let root = tree.root;
+        let mut anc = vec![(0..n)
+            .map(|v| if v == root { v } else { tree.par[v] })
+            .collect::<Vec<_>>()];
+        let mut up = vec![(0..n)
+            .map(|v| if v == root { T::id() } else { weight(v) })
+            .collect::<Vec<_>>()];
+        let max_depth = tree.depth.iter().copied().max().unwrap_or(0);
+        while 1 << anc.len() <= max_depth {
+            let (a, u) = (anc.last().unwrap(), up.last().unwrap());
+            let next_a = (0..n).map(|v| a[a[v]]).collect();
+            let next_u = (0..n).map(|v| u[v].op(&u[a[v]])).collect();
+            anc.push(next_a);
+            up.push(next_u);
+        }
+        Self {
+            depth: tree.depth,
+            anc,
+            up,
+            root,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.depth.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.depth.is_empty()
+    }
+
+    pub fn parent(&self, v: usize) -> Option<usize> {
+        (v != self.root).then(|| self.anc[0][v])
+    }
+
+    pub fn depth(&self, v: usize) -> usize {
+        self.depth[v]
+    }
+
+    /// The `k`-th ancestor of `v` and the product of the `k` edges passed,
+    /// from `v` upwards. Panics if `k > depth(v)`.
+    pub fn ascend(&self, mut v: usize, k: usize) -> (usize, T) {
+        assert!(k <= self.depth[v]);
+        let mut acc = T::id();
+        for (i, (anc, up)) in self.anc.iter().zip(&self.up).enumerate() {
+            if k >> i & 1 == 1 {
+                acc = acc.op(&up[v]);
+                v = anc[v];
+            }
+        }
+        (v, acc)
+    }
+
+    pub fn kth_ancestor(&self, v: usize, k: usize) -> Option<usize> {
+        (k <= self.depth[v]).then(|| self.ascend(v, k).0)
+    }
+
+    pub fn lca(&self, u: usize, v: usize) -> usize {
+        let (u, v) = if self.depth[u] >= self.depth[v] {
+            (u, v)
+        } else {
+            (v, u)
+        };
+        let mut u = self.ascend(u, self.depth[u] - self.depth[v]).0;
+        let mut v = v;
+        if u == v {
+            return u;
+        }
+        for anc in self.anc.iter().rev() {
+            if anc[u] != anc[v] {
+                u = anc[u];
+                v = anc[v];
+            }
+        }
+        self.anc[0][u]
+    }
+
+    pub fn dist(&self, u: usize, v: usize) -> usize {
+        self.depth[u] + self.depth[v] - 2 * self.depth[self.lca(u, v)]
+    }
+
+    /// The `k`-th vertex on the path from `u` to `v`, where `u` itself is the
+    /// 0th.
+    pub fn jump(&self, u: usize, v: usize, k: usize) -> Option<usize> {
+        let w = self.lca(u, v);
+        let du = self.depth[u] - self.depth[w];
+        let dv = self.depth[v] - self.depth[w];
+        if k <= du {
+            self.kth_ancestor(u, k)
+        } else if k <= du + dv {
+            self.kth_ancestor(v, du + dv - k)
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: CommutativeMonoid + Clone> DoublingLca<T> {
+    /// Product of the edge weights on the path between `u` and `v`.
+    pub fn fold_path(&self, u: usize, v: usize) -> T {
+        let w = self.depth[self.lca(u, v)];
+        let (_, a) = self.ascend(u, self.depth[u] - w);
+        let (_, b) = self.ascend(v, self.depth[v] - w);
+        a.op(&b)
+    }
+}
+
+/// Answers LCA queries offline by Tarjan's algorithm in O((n + q) α(n)).
+pub fn offline_lca(par: &[usize], queries: &[(usize, usize)]) -> Vec<usize> {
+    let tree = Tree::from_parents(par);
+    let n = par.len();
+    let mut qstart = vec![0; n + 1];
+    for &(u, v) in queries {
+        qstart[u + 1] += 1;
+        qstart[v + 1] += 1;
+    }
+    for i in 0..n {
+        qstart[i + 1] += qstart[i];
+    }
+    let mut fill = qstart.clone();
+    let mut qs = vec![0; 2 * queries.len()];
+    for (i, &(u, v)) in queries.iter().enumerate() {
+        qs[fill[u]] = i;
+        fill[u] += 1;
+        qs[fill[v]] = i;
+        fill[v] += 1;
+    }
+    let mut res = vec![NIL; queries.len()];
+    let mut dsu = Dsu::new(n);
+    // Representative of each DSU set, i.e. the ancestor being processed.
+    let mut top: Vec<usize> = (0..n).collect();
+    let mut done = vec![false; n];
+    // Iterative post-order: `(v, false)` enters and `(v, true)` leaves.
+    let mut stack = vec![];
+    if n > 0 {
+        stack.push((tree.root, false));
+    }
+    while let Some((v, leave)) = stack.pop() {
+        if !leave {
+            stack.push((v, true));
+            stack.extend(tree.children(v).iter().map(|&c| (c, false)));
+            continue;
+        }
+        done[v] = true;
+        for &i in &qs[qstart[v]..qstart[v + 1]] {
+            let (a, b) = queries[i];
+            let other = if a == v { b } else { a };
+            if done[other] {
+                res[i] = top[dsu.root(other)];
+            }
+        }
+        let p = tree.par[v];
+        if p != NIL {
+            dsu.unite(p, v);
+            top[dsu.root(p)] = p;
+        }
+    }
+    res
+}
@@ -7,46 +7,60 @@
 
 // pub mod arena_slices;
 // pub mod array_vec;
+pub mod algebra;
 pub mod bits;
 pub mod bounded;
 pub mod dijkstra;
 pub mod dsu;
 pub mod fact;
 pub mod fenwick_tree;
-// pub mod graph;
+pub mod graph;
 pub mod input;
 pub mod jagged;
 pub mod kd_tree;
+pub mod lazy_seg_tree;
+pub mod lca;
 pub mod macros;
 // pub mod matrix;
 pub mod cht;
 pub mod complex;
+pub mod coord_comp;
 pub mod d2;
 pub mod d3;
 pub mod disjoint_sparse_table;
+pub mod dynamic_segtree;
 pub mod experimental;
 pub mod f2;
+pub mod factorize;
 pub mod hld;
 pub mod int;
 pub mod li_chao;
+pub mod linear_recurrence;
+pub mod lowlink;
 pub mod mat_util;
 pub mod max_flow;
 pub mod min_cost_flow;
+pub mod mo;
 pub mod mod_int;
+pub mod mod_solve;
+pub mod mst;
 pub mod parser;
 pub mod permutation;
+pub mod persistent;
 pub mod poly;
+pub mod primes;
 pub mod push_relabel;
 pub mod random;
 pub mod rational;
 pub mod scc;
 pub mod segtree;
+pub mod shortest_path;
 pub mod trie;
+pub mod tsort;
 pub mod two_sat;
 pub mod util;
 
 pub mod graph2;
-pub mod graph3;
 pub mod rbstree;
 pub mod slice_arena;
 
@@ -64,6 +78,8 @@ pub mod bitset;
 
 pub mod dft2;
 pub mod modint2;
+pub mod modint64;
+pub mod montgomery;
 pub mod modint_poly;
 
 pub mod bipartite_matching;
@@ -75,7 +91,6 @@ pub mod sa;
 pub mod sandbox;
 pub mod simple_rng;
 pub mod slope_trick;
-pub mod stable_graph;
 
 /*
 #[cfg(feature = "nightly")]
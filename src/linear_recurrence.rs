@@ -0,0 +1,81 @@
+use crate::{modint2::*, modint_poly::Poly};
+
+/// Shortest recurrence `sum_j q[j] s[i - j] = 0` (`i >= l`) with `q[0] = 1`
+/// that generates `s`. The result is not normalized: `len() - 1` is the
+/// order `l`.
+pub fn berlekamp_massey<M: Modulo>(s: &[ModInt<M>]) -> Poly<M> {
+    let mut c = vec![ModInt::ONE];
+    let mut b = vec![ModInt::ONE];
+    let mut l = 0;
+    let mut m = 1;
+    let mut bd = ModInt::ONE;
+    for i in 0..s.len() {
+        let mut d = s[i];
+        for j in 1..=l {
+            d += c[j] * s[i - j];
+        }
+        if d.get() == 0 {
+            m += 1;
+            continue;
+        }
+        let coef = d / bd;
+        let t = c.clone();
+        if c.len() < b.len() + m {
+            c.resize(b.len() + m, ModInt::ZERO);
+        }
+        for (j, &x) in b.iter().enumerate() {
+            c[j + m] -= coef * x;
+        }
+        if 2 * l <= i {
+            l = i + 1 - l;
+            b = t;
+            bd = d;
+            m = 1;
+        } else {
+            m += 1;
+        }
+    }
+    c.resize(l + 1, ModInt::ZERO);
+    c.into_iter().collect()
+}
+
+/// `[x^k] p / q` by Bostan-Mori in O(d log d log k). `q[0]` must not be 0.
+pub fn kth_term<M: Modulo>(p: &Poly<M>, q: &Poly<M>, mut k: u64) -> ModInt<M> {
+    assert!(q.coef(0).get() != 0);
+    let mut p = p.clone();
+    let mut q = q.clone();
+    while k > 0 {
+        let q_neg: Poly<M> = q
+            .iter()
+            .enumerate()
+            .map(|(i, &a)| if i % 2 == 0 { a } else { -a })
+            .collect();
+        let u = &p * &q_neg;
+        let v = &q * &q_neg;
+        p = u
+            .iter()
+            .skip((k & 1) as usize)
+            .step_by(2)
+            .copied()
+            .collect();
+        q = v.iter().step_by(2).copied().collect();
+        k >>= 1;
+    }
+    p.coef(0) / q.coef(0)
+}
+
+/// The `k`-th term of the linear recurrent sequence starting with `s`.
+/// `s` should contain at least twice the order of the recurrence.
+pub fn nth_term<M: Modulo>(s: &[ModInt<M>], k: u64) -> ModInt<M> {
+    if k < s.len() as u64 {
+        return s[k as usize];
+    }
+    let q = berlekamp_massey(s);
+    let l = q.len() - 1;
+    if l == 0 {
+        return ModInt::ZERO;
+    }
+    let mut p = Poly::from(s[..l].to_vec()) * &q;
+    p.truncate(l - 1);
+    kth_term(&p, &q, k)
+}
@@ -0,0 +1,191 @@
+use crate::graph2::IndexedGraph;
+
+const NIL: usize = !0;
+
+/// DFS order and low-link values of an undirected graph, for bridges,
+/// articulation points and the 2-edge/2-vertex-connected decompositions.
+///
+/// `adj(u)` lists the neighbors of `u` once per edge. One occurrence of the
+/// DFS parent is taken as the tree edge, so parallel edges are handled.
+pub struct Lowlink {
+    ord: Vec<usize>,
+    low: Vec<usize>,
+    parent: Vec<usize>,
+    preorder: Vec<usize>,
+}
+
+impl Lowlink {
+    pub fn new<A, I>(num_verts: usize, adj: A) -> Self
+    where
+        A: FnMut(usize) -> I,
+        I: IntoIterator<Item = usize>,
+    {
+        let mut dfs = Dfs {
+            adj,
+            ll: Lowlink {
+                ord: vec![NIL; num_verts],
+                low: vec![NIL; num_verts],
+                parent: vec![NIL; num_verts],
+                preorder: Vec::with_capacity(num_verts),
+            },
+        };
+        for r in 0..num_verts {
+            if dfs.ll.ord[r] == NIL {
+                dfs.rec(r, NIL);
+            }
+        }
+        dfs.ll
+    }
+
+    /// `new` on the neighbors of an `IndexedGraph`.
+    pub fn from_graph<G: IndexedGraph + ?Sized>(g: &G) -> Self {
+        Self::new(g.num_nodes(), |u| g.neighbors(u))
+    }
+
+    pub fn num_verts(&self) -> usize {
+        self.ord.len()
+    }
+
+    /// Position of `u` in the DFS preorder.
+    pub fn ord(&self, u: usize) -> usize {
+        self.ord[u]
+    }
+
+    pub fn low(&self, u: usize) -> usize {
+        self.low[u]
+    }
+
+    /// Parent in the DFS forest.
+    pub fn parent(&self, u: usize) -> Option<usize> {
+        Some(self.parent[u]).filter(|&p| p != NIL)
+    }
+
+    /// Bridges as `(parent, child)` tree edges.
+    pub fn bridges(&self) -> Vec<(usize, usize)> {
+        self.preorder
+            .iter()
+            .filter_map(|&v| {
+                let p = self.parent(v)?;
+                (self.low[v] > self.ord[p]).then_some((p, v))
+            })
+            .collect()
+    }
+
+    /// Articulation points in increasing order.
+    pub fn articulation_points(&self) -> Vec<usize> {
+        let n = self.num_verts();
+        let mut children = vec![0; n];
+        let mut cut = vec![false; n];
+        for &v in &self.preorder {
+            if let Some(p) = self.parent(v) {
+                children[p] += 1;
+                if self.parent[p] != NIL && self.low[v] >= self.ord[p] {
+                    cut[p] = true;
+                }
+            }
+        }
+        (0..n)
+            .filter(|&u| cut[u] || (self.parent[u] == NIL && children[u] >= 2))
+            .collect()
+    }
+
+    /// `(count, id)` of the 2-edge-connected components, in the same shape
+    /// as `scc::scc`.
+    pub fn two_edge_connected_components(&self) -> (usize, Vec<usize>) {
+        let mut id = vec![0; self.num_verts()];
+        let mut count = 0;
+        for &v in &self.preorder {
+            match self.parent(v) {
+                Some(p) if self.low[v] <= self.ord[p] => id[v] = id[p],
+                _ => {
+                    id[v] = count;
+                    count += 1;
+                }
+            }
+        }
+        (count, id)
+    }
+
+    /// Vertex sets of the biconnected components. An articulation point
+    /// belongs to several of them, and an isolated vertex forms its own.
+    pub fn biconnected_components(&self) -> Vec<Vec<usize>> {
+        let mut blocks: Vec<Vec<usize>> = vec![];
+        // Block of the tree edge from the parent of `v` to `v`.
+        let mut block = vec![NIL; self.num_verts()];
+        for &v in &self.preorder {
+            match self.parent(v) {
+                None => {
+                    block[v] = blocks.len();
+                    blocks.push(vec![v]);
+                }
+                Some(p) => {
+                    if self.low[v] >= self.ord[p] {
+                        block[v] = blocks.len();
+                        blocks.push(vec![p]);
+                    } else {
+                        block[v] = block[p];
+                    }
+                    blocks[block[v]].push(v);
+                }
+            }
+        }
+        // A root with children only seeds the blocks of its tree edges.
+        let mut has_child = vec![false; self.num_verts()];
+        for &v in &self.preorder {
+            if let Some(p) = self.parent(v) {
+                has_child[p] = true;
+            }
+        }
+        blocks
+            .into_iter()
+            .filter(|b| !(b.len() == 1 && has_child[b[0]]))
+            .collect()
+    }
+
+    /// Block-cut tree with the vertices as nodes `0..n` and the biconnected
+    /// components as nodes `n..`, each joined to the vertices it contains.
+    pub fn block_cut_tree(&self) -> Vec<Vec<usize>> {
+        let n = self.num_verts();
+        let blocks = self.biconnected_components();
+        let mut g = vec![vec![]; n + blocks.len()];
+        for (i, b) in blocks.iter().enumerate() {
+            for &v in b {
+                g[v].push(n + i);
+                g[n + i].push(v);
+            }
+        }
+        g
+    }
+}
+
+struct Dfs<A> {
+    adj: A,
+    ll: Lowlink,
+}
+
+impl<A, I> Dfs<A>
+where
+    A: FnMut(usize) -> I,
+    I: IntoIterator<Item = usize>,
+{
+    fn rec(&mut self, u: usize, p: usize) {
+        let ll = &mut self.ll;
+        ll.ord[u] = ll.preorder.len();
+        ll.low[u] = ll.ord[u];
+        ll.parent[u] = p;
+        ll.preorder.push(u);
+        let mut parent_edge = p != NIL;
+        for v in (self.adj)(u) {
+            if v == p && parent_edge {
+                parent_edge = false;
+                continue;
+            }
+            if self.ll.ord[v] == NIL {
+                self.rec(v, u);
+                self.ll.low[u] = self.ll.low[u].min(self.ll.low[v]);
+            } else {
+                self.ll.low[u] = self.ll.low[u].min(self.ll.ord[v]);
+            }
+        }
+    }
+}
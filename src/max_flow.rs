@@ -1,5 +1,7 @@
 use std::collections::VecDeque;
 
+use crate::graph2::WeightedGraph;
+
 pub struct MaxFlow {
     g: Vec<Vec<Edge>>,
 }
@@ -54,6 +56,17 @@ impl MaxFlow {
         Self { g: vec![vec![]; n] }
     }
 
+    /// Network with an edge of each capacity in `g`.
+    pub fn from_graph<G: WeightedGraph<u64> + ?Sized>(g: &G) -> Self {
+        let mut flow = Self::new(g.num_nodes());
+        for u in g.nodes() {
+            for (v, cap) in g.edges(u) {
+                flow.edge(u, v, cap);
+            }
+        }
+        flow
+    }
+
     pub fn edge(&mut self, u: usize, v: usize, cap: u64) -> EdgeToken {
         let iu = self.g[u].len();
         let iv = self.g[v].len();
@@ -0,0 +1,237 @@
+use crate::hld::Hld;
+
+/// State of the current range `[l, r)`.
+pub trait Mo {
+    fn add_left(&mut self, i: usize);
+    fn add_right(&mut self, i: usize);
+    fn remove_left(&mut self, i: usize);
+    fn remove_right(&mut self, i: usize);
+    fn answer(&mut self, q: usize);
+}
+
+/// State for add-only Mo. Removal is replaced with rolling back to the last
+/// snapshot.
+pub trait MoRollback {
+    fn add_left(&mut self, i: usize);
+    fn add_right(&mut self, i: usize);
+    fn snapshot(&mut self);
+    fn rollback(&mut self);
+    /// Makes the range empty.
+    fn reset(&mut self);
+    fn answer(&mut self, q: usize);
+}
+
+/// State of the set of vertices on the current path. The vertices are added
+/// and removed in no particular order.
+pub trait MoTree {
+    fn add(&mut self, v: usize);
+    fn remove(&mut self, v: usize);
+    fn answer(&mut self, q: usize);
+}
+
+/// Answers the half-open range queries `[l, r)` over `0..n` in the block order.
+pub fn mo<S: Mo + ?Sized>(n: usize, queries: &[(usize, usize)], state: &mut S) {
+    run(queries, &block_order(n, queries), state);
+}
+
+/// Same as `mo`, but in the Hilbert curve order.
+pub fn mo_hilbert<S: Mo + ?Sized>(n: usize, queries: &[(usize, usize)], state: &mut S) {
+    run(queries, &hilbert_order(n, queries), state);
+}
+
+/// Moves the range through `queries` in the given order.
+pub fn run<S: Mo + ?Sized>(queries: &[(usize, usize)], order: &[usize], state: &mut S) {
+    let mut l = 0;
+    let mut r = 0;
+    for &q in order {
+        let (ql, qr) = queries[q];
+        assert!(ql <= qr);
+        while l > ql {
+            l -= 1;
+            state.add_left(l);
+        }
+        while r < qr {
+            state.add_right(r);
+            r += 1;
+        }
+        while l < ql {
+            state.remove_left(l);
+            l += 1;
+        }
+        while r > qr {
+            r -= 1;
+            state.remove_right(r);
+        }
+        state.answer(q);
+    }
+}
+
+pub fn block_order(n: usize, queries: &[(usize, usize)]) -> Vec<usize> {
+    let b = (n as f64 / (queries.len() as f64).sqrt()).ceil().max(1.0) as usize;
+    let mut order: Vec<usize> = (0..queries.len()).collect();
+    order.sort_by_key(|&q| {
+        let (l, r) = queries[q];
+        let bl = l / b;
+        (bl, if bl & 1 == 0 { r } else { !r })
+    });
+    order
+}
+
+pub fn hilbert_order(n: usize, queries: &[(usize, usize)]) -> Vec<usize> {
+    let k = (n + 1).next_power_of_two().trailing_zeros();
+    let keys: Vec<u64> = queries
+        .iter()
+        .map(|&(l, r)| hilbert(k, l as u64, r as u64))
+        .collect();
+    let mut order: Vec<usize> = (0..queries.len()).collect();
+    order.sort_unstable_by_key(|&q| keys[q]);
+    order
+}
+
+fn hilbert(k: u32, mut x: u64, mut y: u64) -> u64 {
+    let mut d = 0;
+    for s in (0..k).rev() {
+        let rx = (x >> s) & 1;
+        let ry = (y >> s) & 1;
+        d += ((3 * rx) ^ ry) << (2 * s);
+        if ry == 0 {
+            if rx == 1 {
+                x = !x;
+                y = !y;
+            }
+            std::mem::swap(&mut x, &mut y);
+        }
+    }
+    d
+}
+
+/// Add-only Mo over `0..n`.
+pub fn mo_rollback<S: MoRollback + ?Sized>(n: usize, queries: &[(usize, usize)], state: &mut S) {
+    let b = (n as f64 / (queries.len() as f64).sqrt()).ceil().max(1.0) as usize;
+    let mut blocks = vec![vec![]; n / b + 1];
+    for (q, &(l, r)) in queries.iter().enumerate() {
+        assert!(l <= r && r <= n);
+        if r - l <= b {
+            state.reset();
+            for i in l..r {
+                state.add_right(i);
+            }
+            state.answer(q);
+        } else {
+            blocks[l / b].push(q);
+        }
+    }
+    for (i, mut qs) in blocks.into_iter().enumerate() {
+        if qs.is_empty() {
+            continue;
+        }
+        qs.sort_unstable_by_key(|&q| queries[q].1);
+        let mid = (i + 1) * b;
+        let mut r = mid;
+        state.reset();
+        for q in qs {
+            let (ql, qr) = queries[q];
+            while r < qr {
+                state.add_right(r);
+                r += 1;
+            }
+            state.snapshot();
+            for j in (ql..mid).rev() {
+                state.add_left(j);
+            }
+            state.answer(q);
+            state.rollback();
+        }
+    }
+}
+
+/// Answers the path queries `(u, v)`, both ends included.
+pub fn mo_tree<S: MoTree + ?Sized>(hld: &Hld, queries: &[(usize, usize)], state: &mut S) {
+    let n = hld.len();
+    let mut tour = Vec::with_capacity(2 * n);
+    let mut tin = vec![0; n];
+    let mut tout = vec![0; n];
+    let mut stack: Vec<usize> = vec![];
+    for &v in hld.order() {
+        while let Some(&u) = stack.last() {
+            if Some(u) == hld.parent(v) {
+                break;
+            }
+            tout[u] = tour.len();
+            tour.push(u);
+            stack.pop();
+        }
+        tin[v] = tour.len();
+        tour.push(v);
+        stack.push(v);
+    }
+    while let Some(u) = stack.pop() {
+        tout[u] = tour.len();
+        tour.push(u);
+    }
+    let mut lcas = Vec::with_capacity(queries.len());
+    let ranges: Vec<(usize, usize)> = queries
+        .iter()
+        .map(|&(u, v)| {
+            let (u, v) = if tin[u] <= tin[v] { (u, v) } else { (v, u) };
+            let w = hld.lca(u, v);
+            if w == u {
+                lcas.push(None);
+                (tin[u], tin[v] + 1)
+            } else {
+                lcas.push(Some(w));
+                (tout[u], tin[v] + 1)
+            }
+        })
+        .collect();
+    let mut toggle = Toggle {
+        inner: state,
+        tour: &tour,
+        lcas: &lcas,
+        on: vec![false; n],
+    };
+    mo_hilbert(tour.len(), &ranges, &mut toggle);
+}
+
+struct Toggle<'a, S: ?Sized> {
+    inner: &'a mut S,
+    tour: &'a [usize],
+    lcas: &'a [Option<usize>],
+    on: Vec<bool>,
+}
+
+impl<S: MoTree + ?Sized> Toggle<'_, S> {
+    fn toggle(&mut self, i: usize) {
+        let v = self.tour[i];
+        self.on[v] = !self.on[v];
+        if self.on[v] {
+            self.inner.add(v);
+        } else {
+            self.inner.remove(v);
+        }
+    }
+}
+
+impl<S: MoTree + ?Sized> Mo for Toggle<'_, S> {
+    fn add_left(&mut self, i: usize) {
+        self.toggle(i);
+    }
+    fn add_right(&mut self, i: usize) {
+        self.toggle(i);
+    }
+    fn remove_left(&mut self, i: usize) {
+        self.toggle(i);
+    }
+    fn remove_right(&mut self, i: usize) {
+        self.toggle(i);
+    }
+    fn answer(&mut self, q: usize) {
+        if let Some(w) = self.lcas[q] {
+            self.inner.add(w);
+            self.inner.answer(q);
+            self.inner.remove(w);
+        } else {
+            self.inner.answer(q);
+        }
+    }
+}
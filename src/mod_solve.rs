@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+
+use crate::{factorize::factorize_rho, int::gcd, montgomery::inv_mod};
+
+#[inline]
+fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    (a as u128 * b as u128 % m as u128) as u64
+}
+
+fn pow_mod(mut a: u64, mut e: u64, m: u64) -> u64 {
+    let mut res = 1 % m;
+    while e > 0 {
+        if e & 1 == 1 {
+            res = mul_mod(res, a, m);
+        }
+        a = mul_mod(a, a, m);
+        e >>= 1;
+    }
+    res
+}
+
+/// Smallest `x` in `0..=bound` with `k * a^x = b (mod m)`, by baby-step
+/// giant-step. `a` must be coprime to `m`.
+fn bsgs(a: u64, b: u64, k: u64, m: u64, bound: u64) -> Option<u64> {
+    let n = (bound as f64).sqrt() as u64 + 1;
+    let mut baby = HashMap::with_capacity(n as usize);
+    // b * a^j for j in 0..n; later j overwrite, giving the smallest x.
+    let mut x = b;
+    for j in 0..n {
+        baby.insert(x, j);
+        x = mul_mod(x, a, m);
+    }
+    let giant = pow_mod(a, n, m);
+    let mut y = k;
+    for i in 1..=n {
+        y = mul_mod(y, giant, m);
+        if let Some(&j) = baby.get(&y) {
+            let x = i * n - j;
+            return (x <= bound).then_some(x);
+        }
+    }
+    None
+}
+
+/// Smallest `x >= 0` with `a^x = b (mod m)`, in O(sqrt(m)). `a` and `m` need
+/// not be coprime.
+pub fn discrete_log(a: u64, b: u64, m: u64) -> Option<u64> {
+    assert!(m > 0);
+    let (a, mut b, mut m) = (a % m, b % m, m);
+    // Divide out the common factors so that the rest is a unit.
+    let mut k = 1 % m;
+    let mut add = 0;
+    loop {
+        if k == b {
+            return Some(add);
+        }
+        let g = gcd(a, m);
+        if g == 1 {
+            break;
+        }
+        if b % g != 0 {
+            return None;
+        }
+        b /= g;
+        m /= g;
+        k = mul_mod(k, a / g, m);
+        add += 1;
+    }
+    if k == b {
+        return Some(add);
+    }
+    bsgs(a % m, b, k, m, m).map(|x| x + add)
+}
+
+/// A square root of `a` modulo the prime `p`, the smaller of the two, by
+/// Tonelli-Shanks.
+pub fn sqrt_mod(a: u64, p: u64) -> Option<u64> {
+    let a = a % p;
+    if a < 2 || p == 2 {
+        return Some(a);
+    }
+    if pow_mod(a, (p - 1) / 2, p) != 1 {
+        return None;
+    }
+    let s = (p - 1).trailing_zeros();
+    let q = (p - 1) >> s;
+    let z = (2..).find(|&z| pow_mod(z, (p - 1) / 2, p) != 1).unwrap();
+    let mut c = pow_mod(z, q, p);
+    let mut x = pow_mod(a, q.div_ceil(2), p);
+    let mut t = pow_mod(a, q, p);
+    let mut m = s;
+    while t != 1 {
+        let mut i = 0;
+        let mut t2 = t;
+        while t2 != 1 {
+            t2 = mul_mod(t2, t2, p);
+            i += 1;
+        }
+        let b = pow_mod(c, 1 << (m - i - 1), p);
+        x = mul_mod(x, b, p);
+        c = mul_mod(b, b, p);
+        t = mul_mod(t, c, p);
+        m = i;
+    }
+    Some(x.min(p - x))
+}
+
+/// A `q^e`-th root of the `q^e`-th power residue `a` modulo the prime `p`,
+/// where `q` is a prime and `q^e` divides `p - 1` (Adleman-Manders-Miller).
+#[allow(clippy::manual_is_multiple_of)]
+fn prime_power_root(a: u64, q: u64, e: u32, p: u64) -> u64 {
+    let mut s = 0;
+    let mut t = p - 1;
+    while t % q == 0 {
+        t /= q;
+        s += 1;
+    }
+    let qe = q.pow(e);
+    // x^(q^e) / a lies in the subgroup of order q^(s - e).
+    let u = if t == 1 { 0 } else { inv_mod(qe % t, t) };
+    let mut x = pow_mod(a, u, p);
+    let mut err = mul_mod(pow_mod(x, qe, p), inv_mod(a, p), p);
+    let z = (2..).find(|&z| pow_mod(z, (p - 1) / q, p) != 1).unwrap();
+    // `c` generates the subgroup of order q^s and `g` the one of order q.
+    let c = pow_mod(z, t, p);
+    let g = pow_mod(c, q.pow(s - 1), p);
+    while err != 1 {
+        let mut k = 0;
+        let mut h = err;
+        while h != 1 {
+            h = pow_mod(h, q, p);
+            k += 1;
+        }
+        let h = pow_mod(err, q.pow(k - 1), p);
+        let j = bsgs(g, h, 1, p, q - 1).unwrap();
+        // w^(q^e) = c^(j q^(s - k)) cancels the top of the order of `err`.
+        let w = pow_mod(pow_mod(c, q.pow(s - k - e), p), j, p);
+        let w_inv = inv_mod(w, p);
+        x = mul_mod(x, w_inv, p);
+        err = mul_mod(err, pow_mod(w_inv, qe, p), p);
+    }
+    x
+}
+
+/// Some `x` with `x^k = a (mod p)` for a prime `p`.
+pub fn kth_root_mod(a: u64, k: u64, p: u64) -> Option<u64> {
+    let a = a % p;
+    if k == 0 {
+        return (a == 1 % p).then_some(1 % p);
+    }
+    if a < 2 || p == 2 {
+        return Some(a);
+    }
+    let d = gcd(k, p - 1);
+    if pow_mod(a, (p - 1) / d, p) != 1 {
+        return None;
+    }
+    // Take the d-th root one prime power at a time, then undo the part of k
+    // coprime to p - 1.
+    let mut y = a;
+    for (q, e) in factorize_rho(d) {
+        y = prime_power_root(y, q, e, p);
+    }
+    let r = (p - 1) / d;
+    let u = if r == 1 { 0 } else { inv_mod(k / d % r, r) };
+    Some(pow_mod(y, u, p))
+}
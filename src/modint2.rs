@@ -7,6 +7,8 @@ use std::{
     ops,
 };
 
+use crate::mod_solve;
+
 #[inline]
 pub fn mint<const M: u32>(value: impl Into<ModInt<ConstMod<M>>>) -> ModInt<ConstMod<M>> {
     value.into()
@@ -188,6 +190,20 @@ impl<M: Modulo> ModInt<M> {
     pub fn inv(self) -> Self {
         self.pow(M::modulo() - 2)
     }
+    /// The smaller square root, `min(x, p - x)`. The modulus must be prime.
+    pub fn sqrt(self) -> Option<Self> {
+        mod_solve::sqrt_mod(self.value as u64, M::modulo() as u64)
+            .map(|x| Self::unnormalized(x as u32))
+    }
+    /// Smallest `x >= 0` with `self^x = b`.
+    pub fn discrete_log(self, b: Self) -> Option<u64> {
+        mod_solve::discrete_log(self.value as u64, b.value as u64, M::modulo() as u64)
+    }
+    /// Some `x` with `x^k = self`. The modulus must be prime.
+    pub fn kth_root(self, k: u64) -> Option<Self> {
+        mod_solve::kth_root_mod(self.value as u64, k, M::modulo() as u64)
+            .map(|x| Self::unnormalized(x as u32))
+    }
     /// experimental
     /// <https://en.wikipedia.org/wiki/Thue%27s_lemma>
     pub fn fraction(self) -> (i32, i32) {
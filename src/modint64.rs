@@ -0,0 +1,303 @@
+use std::{cell::Cell, cmp, fmt, hash::Hash, iter, marker::PhantomData, ops};
+
+use crate::{modint2::Pow, montgomery::inv_mod};
+
+pub trait Modulo64 {
+    fn modulo() -> u64;
+}
+
+pub struct ConstMod64<const M: u64>;
+
+impl<const M: u64> Modulo64 for ConstMod64<M> {
+    #[inline]
+    fn modulo() -> u64 {
+        M
+    }
+}
+
+thread_local! {
+    static VAR: Cell<u64> = const { Cell::new(1) };
+}
+
+pub struct VarMod64;
+
+impl Modulo64 for VarMod64 {
+    #[inline]
+    fn modulo() -> u64 {
+        VAR.with(|m| m.get())
+    }
+}
+
+pub fn set_var_mod64(m: u64) {
+    assert!((1..=1 << 62).contains(&m));
+    VAR.with(|v| v.set(m));
+}
+
+pub type Mint64<const M: u64> = ModInt64<ConstMod64<M>>;
+
+/// `ModInt` for moduli up to 2^62. Multiplication goes through `u128`.
+#[repr(transparent)]
+pub struct ModInt64<M> {
+    value: u64,
+    marker: PhantomData<M>,
+}
+
+impl<M> ModInt64<M> {
+    pub const ZERO: Self = Self::unnormalized(0);
+    pub const ONE: Self = Self::unnormalized(1);
+    #[inline]
+    pub const fn unnormalized(value: u64) -> Self {
+        Self {
+            value,
+            marker: PhantomData,
+        }
+    }
+    #[inline]
+    pub const fn get(self) -> u64 {
+        self.value
+    }
+}
+
+impl<M: Modulo64> ModInt64<M> {
+    #[inline]
+    pub fn new(value: u64) -> Self {
+        Self::unnormalized(value % M::modulo())
+    }
+    #[inline]
+    pub fn modulo() -> u64 {
+        M::modulo()
+    }
+    /// Panics if the value is not invertible.
+    pub fn inv(self) -> Self {
+        Self::unnormalized(inv_mod(self.value, M::modulo()))
+    }
+}
+
+impl<M: Modulo64> ops::Neg for ModInt64<M> {
+    type Output = Self;
+    #[inline]
+    fn neg(self) -> Self {
+        Self::unnormalized(if self.value == 0 {
+            0
+        } else {
+            M::modulo() - self.value
+        })
+    }
+}
+
+impl<M: Modulo64> ops::Add for ModInt64<M> {
+    type Output = Self;
+    #[inline]
+    fn add(self, other: Self) -> Self {
+        let sum = self.value + other.value;
+        Self::unnormalized(if sum < M::modulo() {
+            sum
+        } else {
+            sum - M::modulo()
+        })
+    }
+}
+
+impl<M: Modulo64> ops::Sub for ModInt64<M> {
+    type Output = Self;
+    #[inline]
+    fn sub(self, other: Self) -> Self {
+        let (diff, of) = self.value.overflowing_sub(other.value);
+        Self::unnormalized(if of {
+            diff.wrapping_add(M::modulo())
+        } else {
+            diff
+        })
+    }
+}
+
+impl<M: Modulo64> ops::Mul for ModInt64<M> {
+    type Output = Self;
+    #[inline]
+    fn mul(self, other: Self) -> Self {
+        Self::unnormalized((self.value as u128 * other.value as u128 % M::modulo() as u128) as u64)
+    }
+}
+
+impl<M: Modulo64> ops::Div for ModInt64<M> {
+    type Output = Self;
+    #[inline]
+    fn div(self, other: Self) -> Self {
+        self * other.inv()
+    }
+}
+
+macro_rules! binop {
+    ($Op:ident, $op:ident, $OpAssign:ident, $op_assign:ident) => {
+        impl<M: Modulo64> ops::$Op<&ModInt64<M>> for ModInt64<M> {
+            type Output = Self;
+            #[inline]
+            fn $op(self, other: &Self) -> Self {
+                self.$op(*other)
+            }
+        }
+        impl<M: Modulo64> ops::$Op for &ModInt64<M> {
+            type Output = ModInt64<M>;
+            #[inline]
+            fn $op(self, other: Self) -> ModInt64<M> {
+                (*self).$op(*other)
+            }
+        }
+        impl<M: Modulo64> ops::$OpAssign for ModInt64<M> {
+            #[inline]
+            fn $op_assign(&mut self, rhs: Self) {
+                *self = <Self as ops::$Op>::$op(*self, rhs);
+            }
+        }
+        impl<M: Modulo64> ops::$OpAssign<&ModInt64<M>> for ModInt64<M> {
+            #[inline]
+            fn $op_assign(&mut self, rhs: &Self) {
+                *self = <Self as ops::$Op>::$op(*self, *rhs);
+            }
+        }
+    };
+}
+
+binop!(Add, add, AddAssign, add_assign);
+binop!(Sub, sub, SubAssign, sub_assign);
+binop!(Mul, mul, MulAssign, mul_assign);
+binop!(Div, div, DivAssign, div_assign);
+
+impl<M: Modulo64> iter::Sum for ModInt64<M> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |x, y| x + y)
+    }
+}
+
+impl<M: Modulo64> iter::Product for ModInt64<M> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::new(1), |x, y| x * y)
+    }
+}
+
+impl<'a, M: Modulo64 + 'a> iter::Sum<&'a Self> for ModInt64<M> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl<'a, M: Modulo64 + 'a> iter::Product<&'a Self> for ModInt64<M> {
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().product()
+    }
+}
+
+macro_rules! pow {
+    ($Uint:ident) => {
+        impl<M: Modulo64> Pow<$Uint> for ModInt64<M> {
+            #[inline]
+            fn pow(self, mut exp: $Uint) -> Self {
+                let mut res = Self::new(1);
+                let mut base = self;
+                while exp > 0 {
+                    if exp & 1 == 1 {
+                        res *= base;
+                    }
+                    base *= base;
+                    exp >>= 1;
+                }
+                res
+            }
+        }
+    };
+}
+
+pow!(u32);
+pow!(u64);
+pow!(usize);
+
+impl<M> Clone for ModInt64<M> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M> Copy for ModInt64<M> {}
+
+impl<M> Default for ModInt64<M> {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl<M> PartialEq for ModInt64<M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<M> Eq for ModInt64<M> {}
+
+impl<M> PartialOrd for ModInt64<M> {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<M> Ord for ModInt64<M> {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
+impl<M> Hash for ModInt64<M> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.value.hash(state)
+    }
+}
+
+impl<M> fmt::Display for ModInt64<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.value, f)
+    }
+}
+
+impl<M> fmt::Debug for ModInt64<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.value, f)
+    }
+}
+
+macro_rules! from_uint {
+    ($ty:ident) => {
+        impl<M: Modulo64> From<$ty> for ModInt64<M> {
+            fn from(value: $ty) -> Self {
+                Self::unnormalized((value as u128 % M::modulo() as u128) as u64)
+            }
+        }
+    };
+}
+
+from_uint!(u8);
+from_uint!(u16);
+from_uint!(u32);
+from_uint!(u64);
+from_uint!(u128);
+from_uint!(usize);
+
+macro_rules! from_int {
+    ($ty:ident) => {
+        impl<M: Modulo64> From<$ty> for ModInt64<M> {
+            fn from(value: $ty) -> Self {
+                let abs = Self::from(value.unsigned_abs());
+                if value >= 0 {
+                    abs
+                } else {
+                    -abs
+                }
+            }
+        }
+    };
+}
+
+from_int!(i8);
+from_int!(i16);
+from_int!(i32);
+from_int!(i64);
+from_int!(i128);
+from_int!(isize);
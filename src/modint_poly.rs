@@ -8,7 +8,7 @@ use std::{
     },
 };
 
-use crate::modint2::*;
+use crate::{dft2::convolution_mod, modint2::*};
 
 pub struct Poly<M>(Vec<ModInt<M>>);
 
@@ -135,6 +135,249 @@ impl<M: Modulo> Poly<M> {
         let r = self - other * &q;
         (q, r)
     }
+
+    fn prefix(&self, n: usize) -> Self {
+        Self(self.0[..n.min(self.0.len())].to_vec())
+    }
+
+    pub fn derivative(&self) -> Self {
+        Self::from(
+            self.iter()
+                .enumerate()
+                .skip(1)
+                .map(|(i, &a)| a * ModInt::from(i))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// The constant term is 0.
+    pub fn integral(&self) -> Self {
+        let inv = inv_table::<M>(self.len() + 1);
+        Self::from(
+            std::iter::once(ModInt::ZERO)
+                .chain(self.iter().zip(&inv[1..]).map(|(&a, &b)| a * b))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// `log(f) mod x^n`. The constant term must be 1.
+    pub fn log(&self, n: usize) -> Self {
+        assert!(self.coef(0) == ModInt::ONE);
+        if n == 0 {
+            return Self::zero();
+        }
+        let mut d = self.prefix(n).derivative() * self.inv(n);
+        d.0.truncate(n - 1);
+        d.integral()
+    }
+
+    /// `exp(f) mod x^n`. The constant term must be 0.
+    pub fn exp(&self, n: usize) -> Self {
+        assert!(self.coef(0).get() == 0);
+        if n == 0 {
+            return Self::zero();
+        }
+        let mut g = Self(vec![ModInt::ONE]);
+        let mut m = 1;
+        while m < n {
+            m *= 2;
+            let mut h = self.prefix(m) - g.log(m);
+            h += ModInt::ONE;
+            g *= h;
+            g.0.truncate(m);
+        }
+        g.truncate(n - 1);
+        g
+    }
+
+    /// `f^k mod x^n`.
+    pub fn pow(&self, k: u64, n: usize) -> Self {
+        if k == 0 {
+            return Self::from(vec![ModInt::ONE; n.min(1)]);
+        }
+        let Some(d) = self.iter().position(|a| a.get() != 0) else {
+            return Self::zero();
+        };
+        if d as u128 * k as u128 >= n as u128 {
+            return Self::zero();
+        }
+        let shift = d * k as usize;
+        let m = n - shift;
+        let c = self[d];
+        let c_inv = c.inv();
+        let g: Self = self[d..(d + m).min(self.len())]
+            .iter()
+            .map(|&a| a * c_inv)
+            .collect();
+        let mut l = g.log(m);
+        l *= ModInt::from(k);
+        let mut res = l.exp(m);
+        res *= c.pow(k);
+        res <<= shift;
+        res
+    }
+
+    /// Some `g` with `g^2 = f mod x^n`, if one exists.
+    pub fn sqrt(&self, n: usize) -> Option<Self> {
+        let d = match self.iter().position(|a| a.get() != 0) {
+            Some(d) if d < n => d,
+            _ => return Some(Self::zero()),
+        };
+        if d % 2 == 1 {
+            return None;
+        }
+        let c = self[d];
+        let s = c.sqrt()?;
+        let m = n - d / 2;
+        let c_inv = c.inv();
+        let g: Self = self[d..(d + m).min(self.len())]
+            .iter()
+            .map(|&a| a * c_inv)
+            .collect();
+        let inv2 = ModInt::new(2).inv();
+        let mut h = Self(vec![ModInt::ONE]);
+        let mut k = 1;
+        while k < m {
+            k *= 2;
+            let mut t = g.prefix(k) * h.inv(k);
+            t.0.truncate(k);
+            h += &t;
+            h *= inv2;
+        }
+        h.truncate(m - 1);
+        h *= s;
+        h <<= d / 2;
+        Some(h)
+    }
+
+    /// `f(x + c)`. The degree must be less than the modulus.
+    pub fn taylor_shift(&self, c: ModInt<M>) -> Self {
+        let n = self.len();
+        if n == 0 {
+            return Self::zero();
+        }
+        let fact = Fact::<M>::new();
+        let a: Self = (0..n).rev().map(|i| self[i] * fact.fact(i)).collect();
+        let mut cpow = ModInt::ONE;
+        let b: Self = (0..n)
+            .map(|j| {
+                let x = cpow * fact.fact_inv(j);
+                cpow *= c;
+                x
+            })
+            .collect();
+        let mut p = a * b;
+        p.0.resize(n.max(p.len()), ModInt::ZERO);
+        p.0.truncate(n);
+        p.reverse();
+        for (i, x) in p.iter_mut().enumerate() {
+            *x *= fact.fact_inv(i);
+        }
+        p.normalize();
+        p
+    }
+
+    /// Evaluates at every point of `xs` in O(n log^2 n).
+    pub fn evaluate_many(&self, xs: &[ModInt<M>]) -> Vec<ModInt<M>> {
+        SubproductTree::new(xs).evaluate(self)
+    }
+
+    /// The polynomial of degree less than `xs.len()` through every
+    /// `(xs[i], ys[i])`. `xs` must be distinct.
+    pub fn interpolate(xs: &[ModInt<M>], ys: &[ModInt<M>]) -> Self {
+        SubproductTree::new(xs).interpolate(ys)
+    }
+}
+
+/// Products of `x - xs[i]` over every node of a segment tree on `xs`, for
+/// evaluating or interpolating on the same points repeatedly.
+#[derive(Clone, Debug)]
+pub struct SubproductTree<M> {
+    xs: Vec<ModInt<M>>,
+    size: usize,
+    tree: Vec<Poly<M>>,
+}
+
+impl<M: Modulo> SubproductTree<M> {
+    pub fn new(xs: &[ModInt<M>]) -> Self {
+        let size = xs.len().next_power_of_two();
+        let mut tree = vec![Poly::from(vec![ModInt::ONE]); 2 * size];
+        for (t, &x) in tree[size..].iter_mut().zip(xs) {
+            *t = Poly::from(vec![-x, ModInt::ONE]);
+        }
+        for i in (1..size).rev() {
+            tree[i] = &tree[2 * i] * &tree[2 * i + 1];
+        }
+        Self {
+            xs: xs.to_vec(),
+            size,
+            tree,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.xs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.xs.is_empty()
+    }
+
+    /// `prod (x - xs[i])`
+    pub fn product(&self) -> &Poly<M> {
+        &self.tree[1]
+    }
+
+    pub fn evaluate(&self, f: &Poly<M>) -> Vec<ModInt<M>> {
+        let mut res = Vec::with_capacity(self.len());
+        if !self.is_empty() {
+            self.evaluate_rec(1, self.size, f % &self.tree[1], &mut res);
+        }
+        res
+    }
+
+    fn evaluate_rec(&self, i: usize, width: usize, f: Poly<M>, res: &mut Vec<ModInt<M>>) {
+        if res.len() >= self.len() {
+            return;
+        }
+        if i >= self.size {
+            res.push(f.coef(0));
+            return;
+        }
+        if f.len() <= 16 {
+            let end = (res.len() + width).min(self.len());
+            for &x in &self.xs[res.len()..end] {
+                res.push(f.evaluate(x));
+            }
+            return;
+        }
+        self.evaluate_rec(2 * i, width / 2, &f % &self.tree[2 * i], res);
+        self.evaluate_rec(2 * i + 1, width / 2, f % &self.tree[2 * i + 1], res);
+    }
+
+    /// See `Poly::interpolate`.
+    pub fn interpolate(&self, ys: &[ModInt<M>]) -> Poly<M> {
+        assert_eq!(ys.len(), self.len());
+        if self.is_empty() {
+            return Poly::zero();
+        }
+        let d = self.evaluate(&self.tree[1].derivative());
+        let w: Vec<ModInt<M>> = ys.iter().zip(&d).map(|(&y, &d)| y / d).collect();
+        self.interpolate_rec(1, self.size, &w)
+    }
+
+    fn interpolate_rec(&self, i: usize, width: usize, w: &[ModInt<M>]) -> Poly<M> {
+        if i >= self.size {
+            return Poly::from(w.to_vec());
+        }
+        let half = width / 2;
+        if w.len() <= half {
+            return self.interpolate_rec(2 * i, half, w);
+        }
+        let l = self.interpolate_rec(2 * i, half, &w[..half]);
+        let r = self.interpolate_rec(2 * i + 1, half, &w[half..]);
+        l * &self.tree[2 * i + 1] + r * &self.tree[2 * i]
+    }
 }
 
 impl<M> Deref for Poly<M> {
@@ -281,6 +524,10 @@ impl<M: Modulo> MulAssign for Poly<M> {
             return;
         }
         let len = (self.0.len() + rhs.0.len() - 1).next_power_of_two();
+        if (M::modulo() - 1).trailing_zeros() < len.trailing_zeros() {
+            *self = Self::from(convolution_mod(&self.0, &rhs.0));
+            return;
+        }
         self.0.resize(len, ModInt::new(0));
         dft(&mut self.0);
         rhs.0.resize(len, ModInt::new(0));
@@ -499,10 +746,23 @@ impl<M: Modulo> DivAssign<ModInt<M>> for Poly<M> {
     }
 }
 
+/// `inv[i] = 1 / i` for `1 <= i < n`.
+fn inv_table<M: Modulo>(n: usize) -> Vec<ModInt<M>> {
+    let p = M::modulo() as usize;
+    let mut inv = vec![ModInt::ZERO; n.max(2)];
+    inv[1] = ModInt::ONE;
+    for i in 2..n {
+        inv[i] = -inv[p % i] * ModInt::from(p / i);
+    }
+    inv.truncate(n);
+    inv
+}
+
 fn primitive_root<M: Modulo>() -> u32 {
     match M::modulo() {
-        998244353 => 3,
-        _ => todo!(),
+        998244353 | 167772161 | 469762049 => 3,
+        754974721 => 11,
+        _ => M::primitive_root(),
     }
 }
 
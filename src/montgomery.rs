@@ -0,0 +1,474 @@
+use std::{
+    cell::Cell,
+    cmp, fmt,
+    hash::{Hash, Hasher},
+    iter,
+    marker::PhantomData,
+    ops,
+};
+
+use crate::{int::ext_gcd, modint2::Pow};
+
+/// Montgomery reduction for an odd modulus below 2^31.
+#[derive(Clone, Copy, Debug)]
+pub struct Montgomery32 {
+    m: u32,
+    m_neg_inv: u32,
+    r2: u32,
+}
+
+impl Montgomery32 {
+    pub const fn new(m: u32) -> Self {
+        assert!(m % 2 == 1 && m < 1 << 31);
+        // m * m == 1 (mod 8), and each step doubles the correct bits.
+        let mut inv = m;
+        let mut i = 0;
+        while i < 4 {
+            inv = inv.wrapping_mul(2u32.wrapping_sub(m.wrapping_mul(inv)));
+            i += 1;
+        }
+        Self {
+            m,
+            m_neg_inv: inv.wrapping_neg(),
+            r2: ((1u128 << 64) % m as u128) as u32,
+        }
+    }
+
+    /// `x / 2^32 mod m` for `x < m * 2^32`.
+    #[inline]
+    pub const fn reduce(&self, x: u64) -> u32 {
+        let t = (x as u32).wrapping_mul(self.m_neg_inv);
+        let y = ((x + t as u64 * self.m as u64) >> 32) as u32;
+        if y >= self.m {
+            y - self.m
+        } else {
+            y
+        }
+    }
+}
+
+/// Montgomery reduction for an odd modulus below 2^63.
+#[derive(Clone, Copy, Debug)]
+pub struct Montgomery64 {
+    m: u64,
+    m_neg_inv: u64,
+    r2: u64,
+}
+
+impl Montgomery64 {
+    pub const fn new(m: u64) -> Self {
+        assert!(m % 2 == 1 && m < 1 << 63);
+        let mut inv = m;
+        let mut i = 0;
+        while i < 5 {
+            inv = inv.wrapping_mul(2u64.wrapping_sub(m.wrapping_mul(inv)));
+            i += 1;
+        }
+        let r = ((1u128 << 64) % m as u128) as u64;
+        Self {
+            m,
+            m_neg_inv: inv.wrapping_neg(),
+            r2: (r as u128 * r as u128 % m as u128) as u64,
+        }
+    }
+
+    /// `x / 2^64 mod m` for `x < m * 2^64`.
+    #[inline]
+    pub const fn reduce(&self, x: u128) -> u64 {
+        let t = (x as u64).wrapping_mul(self.m_neg_inv);
+        let y = ((x + t as u128 * self.m as u128) >> 64) as u64;
+        if y >= self.m {
+            y - self.m
+        } else {
+            y
+        }
+    }
+}
+
+/// Arithmetic in Montgomery form shared by the 32-bit and 64-bit reductions.
+pub trait MontgomeryReduction: Copy {
+    type Int: Copy + Eq + Ord + Hash + Default + fmt::Debug;
+    fn modulo(&self) -> u64;
+    fn mul(&self, a: Self::Int, b: Self::Int) -> Self::Int;
+    fn add(&self, a: Self::Int, b: Self::Int) -> Self::Int;
+    fn sub(&self, a: Self::Int, b: Self::Int) -> Self::Int;
+    fn encode(&self, x: u64) -> Self::Int;
+    fn decode(&self, a: Self::Int) -> u64;
+}
+
+macro_rules! reduction {
+    ($Ty:ident, $Int:ident, $Wide:ident) => {
+        impl MontgomeryReduction for $Ty {
+            type Int = $Int;
+            #[inline]
+            fn modulo(&self) -> u64 {
+                self.m as u64
+            }
+            #[inline]
+            fn mul(&self, a: $Int, b: $Int) -> $Int {
+                self.reduce(a as $Wide * b as $Wide)
+            }
+            #[inline]
+            fn add(&self, a: $Int, b: $Int) -> $Int {
+                let s = a + b;
+                if s >= self.m {
+                    s - self.m
+                } else {
+                    s
+                }
+            }
+            #[inline]
+            fn sub(&self, a: $Int, b: $Int) -> $Int {
+                let (d, of) = a.overflowing_sub(b);
+                if of {
+                    d.wrapping_add(self.m)
+                } else {
+                    d
+                }
+            }
+            #[inline]
+            fn encode(&self, x: u64) -> $Int {
+                self.mul((x % self.m as u64) as $Int, self.r2)
+            }
+            #[inline]
+            fn decode(&self, a: $Int) -> u64 {
+                self.reduce(a as $Wide) as u64
+            }
+        }
+    };
+}
+
+reduction!(Montgomery32, u32, u64);
+reduction!(Montgomery64, u64, u128);
+
+pub trait MontgomeryModulo {
+    type R: MontgomeryReduction;
+    fn reduction() -> Self::R;
+}
+
+pub struct ConstMont32<const M: u32>;
+
+impl<const M: u32> ConstMont32<M> {
+    const R: Montgomery32 = Montgomery32::new(M);
+}
+
+impl<const M: u32> MontgomeryModulo for ConstMont32<M> {
+    type R = Montgomery32;
+    #[inline]
+    fn reduction() -> Montgomery32 {
+        Self::R
+    }
+}
+
+pub struct ConstMont64<const M: u64>;
+
+impl<const M: u64> ConstMont64<M> {
+    const R: Montgomery64 = Montgomery64::new(M);
+}
+
+impl<const M: u64> MontgomeryModulo for ConstMont64<M> {
+    type R = Montgomery64;
+    #[inline]
+    fn reduction() -> Montgomery64 {
+        Self::R
+    }
+}
+
+thread_local! {
+    static VAR32: Cell<Montgomery32> = const { Cell::new(Montgomery32::new(1)) };
+    static VAR64: Cell<Montgomery64> = const { Cell::new(Montgomery64::new(1)) };
+}
+
+pub struct VarMont32;
+
+impl MontgomeryModulo for VarMont32 {
+    type R = Montgomery32;
+    #[inline]
+    fn reduction() -> Montgomery32 {
+        VAR32.with(|r| r.get())
+    }
+}
+
+pub struct VarMont64;
+
+impl MontgomeryModulo for VarMont64 {
+    type R = Montgomery64;
+    #[inline]
+    fn reduction() -> Montgomery64 {
+        VAR64.with(|r| r.get())
+    }
+}
+
+pub fn set_var_mont32(m: u32) {
+    VAR32.with(|r| r.set(Montgomery32::new(m)));
+}
+
+pub fn set_var_mont64(m: u64) {
+    VAR64.with(|r| r.set(Montgomery64::new(m)));
+}
+
+pub type Mont32<const M: u32> = MontgomeryModInt<ConstMont32<M>>;
+pub type Mont64<const M: u64> = MontgomeryModInt<ConstMont64<M>>;
+
+type Int<M> = <<M as MontgomeryModulo>::R as MontgomeryReduction>::Int;
+
+/// Value kept in Montgomery form, i.e. `x * 2^w mod m` for the word size `w`.
+#[repr(transparent)]
+pub struct MontgomeryModInt<M: MontgomeryModulo> {
+    value: Int<M>,
+    marker: PhantomData<M>,
+}
+
+impl<M: MontgomeryModulo> MontgomeryModInt<M> {
+    #[inline]
+    fn raw(value: Int<M>) -> Self {
+        Self {
+            value,
+            marker: PhantomData,
+        }
+    }
+    #[inline]
+    pub fn new(value: u64) -> Self {
+        Self::raw(M::reduction().encode(value))
+    }
+    #[inline]
+    pub fn zero() -> Self {
+        Self::raw(Int::<M>::default())
+    }
+    #[inline]
+    pub fn one() -> Self {
+        Self::new(1)
+    }
+    #[inline]
+    pub fn get(self) -> u64 {
+        M::reduction().decode(self.value)
+    }
+    #[inline]
+    pub fn modulo() -> u64 {
+        M::reduction().modulo()
+    }
+    /// Panics if the value is not invertible.
+    pub fn inv(self) -> Self {
+        Self::new(inv_mod(self.get(), Self::modulo()))
+    }
+}
+
+/// Inverse of `a` modulo `m` for any `m`. Panics if `gcd(a, m) != 1`.
+pub(crate) fn inv_mod(a: u64, m: u64) -> u64 {
+    let (g, x, _) = ext_gcd(a as i128, m as i128);
+    assert_eq!(g, 1, "not invertible");
+    x.rem_euclid(m as i128) as u64
+}
+
+impl<M: MontgomeryModulo> ops::Neg for MontgomeryModInt<M> {
+    type Output = Self;
+    #[inline]
+    fn neg(self) -> Self {
+        Self::zero() - self
+    }
+}
+
+impl<M: MontgomeryModulo> ops::Add for MontgomeryModInt<M> {
+    type Output = Self;
+    #[inline]
+    fn add(self, other: Self) -> Self {
+        Self::raw(M::reduction().add(self.value, other.value))
+    }
+}
+
+impl<M: MontgomeryModulo> ops::Sub for MontgomeryModInt<M> {
+    type Output = Self;
+    #[inline]
+    fn sub(self, other: Self) -> Self {
+        Self::raw(M::reduction().sub(self.value, other.value))
+    }
+}
+
+impl<M: MontgomeryModulo> ops::Mul for MontgomeryModInt<M> {
+    type Output = Self;
+    #[inline]
+    fn mul(self, other: Self) -> Self {
+        Self::raw(M::reduction().mul(self.value, other.value))
+    }
+}
+
+impl<M: MontgomeryModulo> ops::Div for MontgomeryModInt<M> {
+    type Output = Self;
+    #[inline]
+    fn div(self, other: Self) -> Self {
+        self * other.inv()
+    }
+}
+
+macro_rules! binop {
+    ($Op:ident, $op:ident, $OpAssign:ident, $op_assign:ident) => {
+        impl<M: MontgomeryModulo> ops::$Op<&MontgomeryModInt<M>> for MontgomeryModInt<M> {
+            type Output = Self;
+            #[inline]
+            fn $op(self, other: &Self) -> Self {
+                self.$op(*other)
+            }
+        }
+        impl<M: MontgomeryModulo> ops::$Op for &MontgomeryModInt<M> {
+            type Output = MontgomeryModInt<M>;
+            #[inline]
+            fn $op(self, other: Self) -> MontgomeryModInt<M> {
+                (*self).$op(*other)
+            }
+        }
+        impl<M: MontgomeryModulo> ops::$OpAssign for MontgomeryModInt<M> {
+            #[inline]
+            fn $op_assign(&mut self, rhs: Self) {
+                *self = <Self as ops::$Op>::$op(*self, rhs);
+            }
+        }
+        impl<M: MontgomeryModulo> ops::$OpAssign<&MontgomeryModInt<M>> for MontgomeryModInt<M> {
+            #[inline]
+            fn $op_assign(&mut self, rhs: &Self) {
+                *self = <Self as ops::$Op>::$op(*self, *rhs);
+            }
+        }
+    };
+}
+
+binop!(Add, add, AddAssign, add_assign);
+binop!(Sub, sub, SubAssign, sub_assign);
+binop!(Mul, mul, MulAssign, mul_assign);
+binop!(Div, div, DivAssign, div_assign);
+
+impl<M: MontgomeryModulo> iter::Sum for MontgomeryModInt<M> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |x, y| x + y)
+    }
+}
+
+impl<M: MontgomeryModulo> iter::Product for MontgomeryModInt<M> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::one(), |x, y| x * y)
+    }
+}
+
+impl<'a, M: MontgomeryModulo + 'a> iter::Sum<&'a Self> for MontgomeryModInt<M> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl<'a, M: MontgomeryModulo + 'a> iter::Product<&'a Self> for MontgomeryModInt<M> {
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().product()
+    }
+}
+
+macro_rules! pow {
+    ($Uint:ident) => {
+        impl<M: MontgomeryModulo> Pow<$Uint> for MontgomeryModInt<M> {
+            #[inline]
+            fn pow(self, mut exp: $Uint) -> Self {
+                let mut res = Self::one();
+                let mut base = self;
+                while exp > 0 {
+                    if exp & 1 == 1 {
+                        res *= base;
+                    }
+                    base *= base;
+                    exp >>= 1;
+                }
+                res
+            }
+        }
+    };
+}
+
+pow!(u32);
+pow!(u64);
+pow!(usize);
+
+impl<M: MontgomeryModulo> Clone for MontgomeryModInt<M> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M: MontgomeryModulo> Copy for MontgomeryModInt<M> {}
+
+impl<M: MontgomeryModulo> Default for MontgomeryModInt<M> {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+impl<M: MontgomeryModulo> PartialEq for MontgomeryModInt<M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<M: MontgomeryModulo> Eq for MontgomeryModInt<M> {}
+
+impl<M: MontgomeryModulo> PartialOrd for MontgomeryModInt<M> {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<M: MontgomeryModulo> Ord for MontgomeryModInt<M> {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.get().cmp(&other.get())
+    }
+}
+
+impl<M: MontgomeryModulo> Hash for MontgomeryModInt<M> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state)
+    }
+}
+
+impl<M: MontgomeryModulo> fmt::Display for MontgomeryModInt<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.get(), f)
+    }
+}
+
+impl<M: MontgomeryModulo> fmt::Debug for MontgomeryModInt<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.get(), f)
+    }
+}
+
+macro_rules! from_uint {
+    ($ty:ident) => {
+        impl<M: MontgomeryModulo> From<$ty> for MontgomeryModInt<M> {
+            fn from(value: $ty) -> Self {
+                Self::new(value as u64)
+            }
+        }
+    };
+}
+
+from_uint!(u8);
+from_uint!(u16);
+from_uint!(u32);
+from_uint!(u64);
+from_uint!(usize);
+
+macro_rules! from_int {
+    ($ty:ident) => {
+        impl<M: MontgomeryModulo> From<$ty> for MontgomeryModInt<M> {
+            fn from(value: $ty) -> Self {
+                let abs = Self::new(value.unsigned_abs() as u64);
+                if value >= 0 {
+                    abs
+                } else {
+                    -abs
+                }
+            }
+        }
+    };
+}
+
+from_int!(i8);
+from_int!(i16);
+from_int!(i32);
+from_int!(i64);
+from_int!(isize);
@@ -0,0 +1,303 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use crate::{
+    algebra::Max,
+    dsu::{Dsu, RollbackDsu},
+    graph::LabeledGraph,
+    lca::DoublingLca,
+    shortest_path::Weight,
+};
+
+const NIL: usize = !0;
+
+/// Minimum spanning forest of the undirected graph `edges` on `n` nodes.
+/// Returns the total weight and the indices of the chosen edges in
+/// increasing order.
+pub fn kruskal<W: Weight>(n: usize, edges: &[(usize, usize, W)]) -> (W, Vec<usize>) {
+    let mut ord: Vec<usize> = (0..edges.len()).collect();
+    ord.sort_by_key(|&i| edges[i].2);
+    let mut dsu = Dsu::new(n);
+    let mut total = W::zero();
+    let mut chosen = vec![];
+    for i in ord {
+        let (u, v, w) = edges[i];
+        if dsu.unite(u, v).is_modified() {
+            total = total + w;
+            chosen.push(i);
+        }
+    }
+    chosen.sort_unstable();
+    (total, chosen)
+}
+
+/// Same as `kruskal`, by Prim's algorithm with a binary heap in
+/// O(m log m).
+pub fn prim<W: Weight>(n: usize, edges: &[(usize, usize, W)]) -> (W, Vec<usize>) {
+    let g = LabeledGraph::from_undirected_edges(
+        n,
+        edges.iter().enumerate().map(|(i, &(u, v, _))| (u, v, i)),
+    );
+    let mut visited = vec![false; n];
+    let mut total = W::zero();
+    let mut chosen = vec![];
+    let mut heap = BinaryHeap::new();
+    for s in 0..n {
+        if visited[s] {
+            continue;
+        }
+        visited[s] = true;
+        heap.extend(g[s].iter().map(|&(_, i)| Reverse((edges[i].2, i))));
+        while let Some(Reverse((w, i))) = heap.pop() {
+            let (u, v, _) = edges[i];
+            let t = if visited[u] { v } else { u };
+            if visited[t] {
+                continue;
+            }
+            visited[t] = true;
+            total = total + w;
+            chosen.push(i);
+            heap.extend(
+                g[t].iter()
+                    .filter(|&&(x, _)| !visited[x])
+                    .map(|&(_, i)| Reverse((edges[i].2, i))),
+            );
+        }
+    }
+    chosen.sort_unstable();
+    (total, chosen)
+}
+
+/// Same as `kruskal`, by Borůvka's algorithm in O((m + n log n) log n).
+pub fn boruvka<W: Weight>(n: usize, edges: &[(usize, usize, W)]) -> (W, Vec<usize>) {
+    let mut dsu = Dsu::new(n);
+    let mut total = W::zero();
+    let mut chosen = vec![];
+    let mut best = vec![NIL; n];
+    loop {
+        best.fill(NIL);
+        // `Dsu::root` takes O(log n), so look each node up once per round.
+        let root: Vec<usize> = (0..n).map(|v| dsu.root(v)).collect();
+        for (i, &(u, v, w)) in edges.iter().enumerate() {
+            let (ru, rv) = (root[u], root[v]);
+            if ru == rv {
+                continue;
+            }
+            // Ties are broken by index so that the chosen edges form a forest.
+            for r in [ru, rv] {
+                if best[r] == NIL || (w, i) < (edges[best[r]].2, best[r]) {
+                    best[r] = i;
+                }
+            }
+        }
+        let mut merged = false;
+        for &i in &best {
+            if i != NIL && dsu.unite(edges[i].0, edges[i].1).is_modified() {
+                total = total + edges[i].2;
+                chosen.push(i);
+                merged = true;
+            }
+        }
+        if !merged {
+            break;
+        }
+    }
+    chosen.sort_unstable();
+    (total, chosen)
+}
+
+/// Weight of the lightest spanning forest whose edge set differs from the
+/// minimum one, in O(m log n). `None` if there is no other spanning forest.
+pub fn second_best_mst<W: Weight>(n: usize, edges: &[(usize, usize, W)]) -> Option<W> {
+    let (total, chosen) = kruskal(n, edges);
+    let mut in_tree = vec![false; edges.len()];
+    for &i in &chosen {
+        in_tree[i] = true;
+    }
+    // Root every tree of the forest at the virtual node `n`.
+    let g = LabeledGraph::from_undirected_edges(
+        n,
+        chosen.iter().map(|&i| (edges[i].0, edges[i].1, edges[i].2)),
+    );
+    let mut par = vec![NIL; n + 1];
+    let mut weight = vec![W::min_value(); n + 1];
+    par[n] = n;
+    for s in 0..n {
+        if par[s] != NIL {
+            continue;
+        }
+        par[s] = n;
+        let mut stack = vec![s];
+        while let Some(u) = stack.pop() {
+            for &(v, w) in &g[u] {
+                if par[v] == NIL {
+                    par[v] = u;
+                    weight[v] = w;
+                    stack.push(v);
+                }
+            }
+        }
+    }
+    let lca = DoublingLca::with_weights(&par, |v| Max(weight[v]));
+    edges
+        .iter()
+        .zip(&in_tree)
+        .filter(|&(&(u, v, _), &t)| !t && u != v)
+        .map(|(&(u, v, w), _)| total + w - lca.fold_path(u, v).0)
+        .min()
+}
+
+/// Whether each edge belongs to some minimum spanning forest.
+pub fn in_some_mst<W: Weight>(n: usize, edges: &[(usize, usize, W)]) -> Vec<bool> {
+    let mut ord: Vec<usize> = (0..edges.len()).collect();
+    ord.sort_by_key(|&i| edges[i].2);
+    let mut dsu = Dsu::new(n);
+    let mut res = vec![false; edges.len()];
+    // An edge is usable iff the lighter edges do not connect its ends.
+    for group in ord.chunk_by(|&i, &j| edges[i].2 == edges[j].2) {
+        for &i in group {
+            res[i] = !dsu.is_same(edges[i].0, edges[i].1);
+        }
+        for &i in group {
+            dsu.unite(edges[i].0, edges[i].1);
+        }
+    }
+    res
+}
+
+/// Skew heap of edges with lazy subtraction from a whole heap.
+struct SkewHeap<W> {
+    key: Vec<(W, usize)>,
+    sub: Vec<W>,
+    left: Vec<usize>,
+    right: Vec<usize>,
+    spine: Vec<usize>,
+}
+
+impl<W: Weight> SkewHeap<W> {
+    fn new(keys: Vec<(W, usize)>) -> Self {
+        let m = keys.len();
+        Self {
+            key: keys,
+            sub: vec![W::zero(); m],
+            left: vec![NIL; m],
+            right: vec![NIL; m],
+            spine: vec![],
+        }
+    }
+
+    fn push_down(&mut self, a: usize) {
+        let s = std::mem::replace(&mut self.sub[a], W::zero());
+        if s != W::zero() {
+            self.key[a].0 = self.key[a].0 - s;
+            for c in [self.left[a], self.right[a]] {
+                if c != NIL {
+                    self.sub[c] = self.sub[c] + s;
+                }
+            }
+        }
+    }
+
+    fn top(&mut self, a: usize) -> (W, usize) {
+        self.push_down(a);
+        self.key[a]
+    }
+
+    fn merge(&mut self, mut a: usize, mut b: usize) -> usize {
+        // Merge along the right spines, then swap children bottom-up.
+        while a != NIL && b != NIL {
+            self.push_down(a);
+            self.push_down(b);
+            if self.key[a] > self.key[b] {
+                std::mem::swap(&mut a, &mut b);
+            }
+            self.spine.push(a);
+            a = self.right[a];
+        }
+        let mut cur = if a != NIL { a } else { b };
+        while let Some(x) = self.spine.pop() {
+            self.right[x] = self.left[x];
+            self.left[x] = cur;
+            cur = x;
+        }
+        cur
+    }
+
+    fn pop(&mut self, a: usize) -> usize {
+        self.push_down(a);
+        self.merge(self.left[a], self.right[a])
+    }
+}
+
+/// Minimum spanning arborescence rooted at `root` of the directed graph
+/// `edges` (Chu-Liu/Edmonds with skew heaps) in O(m log n). Returns the total
+/// weight and, for each node, the index of the edge entering it (`!0` for
+/// the root). `None` if some node is unreachable from `root`.
+pub fn min_arborescence<W: Weight>(
+    n: usize,
+    root: usize,
+    edges: &[(usize, usize, W)],
+) -> Option<(W, Vec<usize>)> {
+    let mut heap = SkewHeap::new(edges.iter().enumerate().map(|(i, e)| (e.2, i)).collect());
+    let mut top = vec![NIL; n];
+    for (i, &(_, v, _)) in edges.iter().enumerate() {
+        top[v] = heap.merge(top[v], i);
+    }
+    let mut dsu = RollbackDsu::new(n);
+    let mut total = W::zero();
+    let mut seen = vec![NIL; n];
+    seen[root] = root;
+    let mut path = vec![0; n];
+    let mut chosen = vec![0; n];
+    let mut incoming = vec![NIL; n];
+    // Contracted cycles: the new node, the DSU snapshot and the cycle edges.
+    let mut cycles = vec![];
+    for s in 0..n {
+        let mut u = s;
+        let mut len = 0;
+        while seen[u] == NIL {
+            if top[u] == NIL {
+                return None;
+            }
+            let (w, i) = heap.top(top[u]);
+            heap.sub[top[u]] = w;
+            top[u] = heap.pop(top[u]);
+            chosen[len] = i;
+            path[len] = u;
+            len += 1;
+            seen[u] = s;
+            total = total + w;
+            u = dsu.root(edges[i].0);
+            if seen[u] == s {
+                let end = len;
+                let snapshot = dsu.snapshot();
+                let mut cyc = NIL;
+                loop {
+                    len -= 1;
+                    let x = path[len];
+                    cyc = heap.merge(cyc, top[x]);
+                    if !dsu.unite(u, x).is_modified() {
+                        break;
+                    }
+                }
+                u = dsu.root(u);
+                top[u] = cyc;
+                seen[u] = NIL;
+                cycles.push((u, snapshot, chosen[len..end].to_vec()));
+            }
+        }
+        for &i in &chosen[..len] {
+            incoming[dsu.root(edges[i].1)] = i;
+        }
+    }
+    // Expand the cycles in reverse order of contraction.
+    for (u, snapshot, cycle) in cycles.into_iter().rev() {
+        dsu.rollback(snapshot);
+        let enter = incoming[u];
+        for i in cycle {
+            incoming[dsu.root(edges[i].1)] = i;
+        }
+        incoming[dsu.root(edges[enter].1)] = enter;
+    }
+    incoming[root] = NIL;
+    Some((total, incoming))
+}
@@ -0,0 +1,324 @@
+use crate::algebra::{Group, Monoid};
+
+/// Handle to one version of a persistent structure.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Root(u32);
+
+const NIL: u32 = !0;
+
+#[derive(Clone)]
+struct Node<T> {
+    val: T,
+    ch: [u32; 2],
+}
+
+/// Nodes are never freed, so every old root stays valid.
+#[derive(Clone)]
+struct Arena<T>(Vec<Node<T>>);
+
+impl<T: Clone> Arena<T> {
+    fn alloc(&mut self, val: T, ch: [u32; 2]) -> u32 {
+        self.0.push(Node { val, ch });
+        (self.0.len() - 1) as u32
+    }
+
+    fn copy(&mut self, i: u32) -> u32 {
+        self.0.push(self.0[i as usize].clone());
+        (self.0.len() - 1) as u32
+    }
+
+    fn val(&self, i: u32) -> &T {
+        &self.0[i as usize].val
+    }
+
+    fn ch(&self, i: u32) -> [u32; 2] {
+        self.0[i as usize].ch
+    }
+}
+
+/// Segment tree where every point update returns a new root and leaves the
+/// old versions intact.
+#[derive(Clone)]
+pub struct PersistentSegTree<T> {
+    len: usize,
+    arena: Arena<T>,
+    init: Root,
+}
+
+impl<T: Monoid + Clone> PersistentSegTree<T> {
+    pub fn new(len: usize) -> Self {
+        Self::from(vec![T::id(); len])
+    }
+
+    fn build(&mut self, a: &[T]) -> u32 {
+        if a.len() == 1 {
+            return self.arena.alloc(a[0].clone(), [NIL; 2]);
+        }
+        let (l, r) = a.split_at(a.len() / 2);
+        let l = self.build(l);
+        let r = self.build(r);
+        let val = self.arena.val(l).op(self.arena.val(r));
+        self.arena.alloc(val, [l, r])
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The version built by the constructor.
+    pub fn initial(&self) -> Root {
+        self.init
+    }
+
+    pub fn num_nodes(&self) -> usize {
+        self.arena.0.len()
+    }
+
+    pub fn get(&self, root: Root, i: usize) -> T {
+        assert!(i < self.len);
+        let (mut node, mut lo, mut hi) = (root.0, 0, self.len);
+        while hi - lo > 1 {
+            let mid = lo + (hi - lo) / 2;
+            let [l, r] = self.arena.ch(node);
+            if i < mid {
+                node = l;
+                hi = mid;
+            } else {
+                node = r;
+                lo = mid;
+            }
+        }
+        self.arena.val(node).clone()
+    }
+
+    pub fn set(&mut self, root: Root, i: usize, x: T) -> Root {
+        self.update(root, i, |_| x)
+    }
+
+    pub fn update(&mut self, root: Root, i: usize, f: impl FnOnce(&T) -> T) -> Root {
+        assert!(i < self.len);
+        Root(self.update_rec(root.0, 0, self.len, i, f))
+    }
+
+    fn update_rec(
+        &mut self,
+        node: u32,
+        lo: usize,
+        hi: usize,
+        i: usize,
+        f: impl FnOnce(&T) -> T,
+    ) -> u32 {
+        if hi - lo == 1 {
+            let val = f(self.arena.val(node));
+            return self.arena.alloc(val, [NIL; 2]);
+        }
+        let mid = lo + (hi - lo) / 2;
+        let [mut l, mut r] = self.arena.ch(node);
+        if i < mid {
+            l = self.update_rec(l, lo, mid, i, f);
+        } else {
+            r = self.update_rec(r, mid, hi, i, f);
+        }
+        let val = self.arena.val(l).op(self.arena.val(r));
+        self.arena.alloc(val, [l, r])
+    }
+
+    pub fn sum(&self, root: Root, l: usize, r: usize) -> T {
+        assert!(l <= r && r <= self.len);
+        if l == r {
+            return T::id();
+        }
+        self.sum_rec(root.0, 0, self.len, l, r)
+    }
+
+    fn sum_rec(&self, node: u32, lo: usize, hi: usize, l: usize, r: usize) -> T {
+        if l <= lo && hi <= r {
+            return self.arena.val(node).clone();
+        }
+        let mid = lo + (hi - lo) / 2;
+        let [cl, cr] = self.arena.ch(node);
+        if r <= mid {
+            self.sum_rec(cl, lo, mid, l, r)
+        } else if mid <= l {
+            self.sum_rec(cr, mid, hi, l, r)
+        } else {
+            self.sum_rec(cl, lo, mid, l, r)
+                .op(&self.sum_rec(cr, mid, hi, l, r))
+        }
+    }
+
+    /// Largest `r` such that `pred(sum(root, l, r))`. `pred(T::id())` must hold.
+    pub fn max_right(&self, root: Root, l: usize, mut pred: impl FnMut(&T) -> bool) -> usize {
+        assert!(l <= self.len);
+        let mut acc = T::id();
+        self.max_right_rec(
+            [root.0; 2],
+            0,
+            self.len,
+            l,
+            &mut acc,
+            &|[_, x]| x.clone(),
+            &mut pred,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn max_right_rec(
+        &self,
+        nodes: [u32; 2],
+        lo: usize,
+        hi: usize,
+        l: usize,
+        acc: &mut T,
+        val: &impl Fn([&T; 2]) -> T,
+        pred: &mut impl FnMut(&T) -> bool,
+    ) -> usize {
+        if hi <= l {
+            return hi;
+        }
+        if l <= lo {
+            let s = acc.op(&val(nodes.map(|i| self.arena.val(i))));
+            if pred(&s) {
+                *acc = s;
+                return hi;
+            }
+            if hi - lo == 1 {
+                return lo;
+            }
+        }
+        let mid = lo + (hi - lo) / 2;
+        let [l0, r0] = self.arena.ch(nodes[0]);
+        let [l1, r1] = self.arena.ch(nodes[1]);
+        let res = self.max_right_rec([l0, l1], lo, mid, l, acc, val, pred);
+        if res < mid {
+            return res;
+        }
+        self.max_right_rec([r0, r1], mid, hi, l, acc, val, pred)
+    }
+}
+
+impl<T: Group + Clone> PersistentSegTree<T> {
+    /// `max_right` on the values of `new` minus those of `old`, e.g. the
+    /// counts added between two versions. `T` should be commutative.
+    pub fn max_right_diff(
+        &self,
+        old: Root,
+        new: Root,
+        l: usize,
+        mut pred: impl FnMut(&T) -> bool,
+    ) -> usize {
+        assert!(l <= self.len);
+        let mut acc = T::id();
+        self.max_right_rec(
+            [old.0, new.0],
+            0,
+            self.len,
+            l,
+            &mut acc,
+            &|[x, y]| y.op_inv(x),
+            &mut pred,
+        )
+    }
+}
+
+impl<T: Monoid + Clone> From<Vec<T>> for PersistentSegTree<T> {
+    fn from(a: Vec<T>) -> Self {
+        let mut st = Self {
+            len: a.len(),
+            arena: Arena(Vec::with_capacity(2 * a.len())),
+            init: Root(NIL),
+        };
+        if !a.is_empty() {
+            st.init = Root(st.build(&a));
+        }
+        st
+    }
+}
+
+/// Array with O(log n) access where every update returns a new root.
+///
+/// Element `i` is stored at node `i + 1` of an implicit binary heap, so the
+/// tree has exactly `len` nodes.
+#[derive(Clone)]
+pub struct PersistentArray<T> {
+    len: usize,
+    arena: Arena<T>,
+    init: Root,
+}
+
+impl<T: Clone> PersistentArray<T> {
+    fn build(&mut self, a: &[T], k: usize) -> u32 {
+        if k > a.len() {
+            return NIL;
+        }
+        let l = self.build(a, 2 * k);
+        let r = self.build(a, 2 * k + 1);
+        self.arena.alloc(a[k - 1].clone(), [l, r])
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn initial(&self) -> Root {
+        self.init
+    }
+
+    pub fn num_nodes(&self) -> usize {
+        self.arena.0.len()
+    }
+
+    pub fn get(&self, root: Root, i: usize) -> &T {
+        assert!(i < self.len);
+        let k = i + 1;
+        let mut node = root.0;
+        for b in (0..usize::BITS - 1 - k.leading_zeros()).rev() {
+            node = self.arena.ch(node)[k >> b & 1];
+        }
+        self.arena.val(node)
+    }
+
+    pub fn set(&mut self, root: Root, i: usize, x: T) -> Root {
+        self.update(root, i, |_| x)
+    }
+
+    pub fn update(&mut self, root: Root, i: usize, f: impl FnOnce(&T) -> T) -> Root {
+        assert!(i < self.len);
+        let k = i + 1;
+        let new_root = self.arena.copy(root.0);
+        let mut node = new_root;
+        for b in (0..usize::BITS - 1 - k.leading_zeros()).rev() {
+            let d = k >> b & 1;
+            let c = self.arena.copy(self.arena.ch(node)[d]);
+            self.arena.0[node as usize].ch[d] = c;
+            node = c;
+        }
+        let node = &mut self.arena.0[node as usize];
+        node.val = f(&node.val);
+        Root(new_root)
+    }
+
+    pub fn to_vec(&self, root: Root) -> Vec<T> {
+        (0..self.len).map(|i| self.get(root, i).clone()).collect()
+    }
+}
+
+impl<T: Clone> From<Vec<T>> for PersistentArray<T> {
+    fn from(a: Vec<T>) -> Self {
+        let mut pa = Self {
+            len: a.len(),
+            arena: Arena(Vec::with_capacity(a.len())),
+            init: Root(NIL),
+        };
+        pa.init = Root(pa.build(&a, 1));
+        pa
+    }
+}
@@ -1,3 +1,7 @@
+use std::ops;
+
+use crate::int::UInt;
+
 pub fn primes(n: usize) -> Vec<usize> {
     // 1, 7, 11, 13, 17, 19, 23, 29
     const SKIP: [u8; 8] = [6, 4, 2, 4, 2, 4, 6, 2];
@@ -26,3 +30,180 @@ pub fn primes(n: usize) -> Vec<usize> {
     }
     ps
 }
+
+/// Sieve of Eratosthenes in O(n) that keeps the smallest prime factor of
+/// every integer in `0..=n`.
+#[derive(Clone, Debug)]
+pub struct LinearSieve {
+    spf: Vec<u32>,
+    primes: Vec<u32>,
+}
+
+impl LinearSieve {
+    pub fn new(n: usize) -> Self {
+        assert!(n < u32::MAX as usize);
+        let mut spf = vec![0u32; n + 1];
+        let mut primes = vec![];
+        for i in 2..=n {
+            if spf[i] == 0 {
+                spf[i] = i as u32;
+                primes.push(i as u32);
+            }
+            for &p in &primes {
+                if p > spf[i] || i * p as usize > n {
+                    break;
+                }
+                spf[i * p as usize] = p;
+            }
+        }
+        Self { spf, primes }
+    }
+
+    /// The sieve covers `0..=max()`.
+    pub fn max(&self) -> usize {
+        self.spf.len() - 1
+    }
+
+    pub fn primes(&self) -> &[u32] {
+        &self.primes
+    }
+
+    pub fn is_prime(&self, x: usize) -> bool {
+        x >= 2 && self.spf[x] as usize == x
+    }
+
+    /// Smallest prime factor of `x >= 2`.
+    pub fn spf(&self, x: usize) -> usize {
+        assert!(x >= 2);
+        self.spf[x] as usize
+    }
+
+    /// Prime factorization of `x >= 1` as `(p, e)` pairs sorted by `p`.
+    #[allow(clippy::manual_is_multiple_of)]
+    pub fn factorize(&self, mut x: usize) -> Vec<(usize, u32)> {
+        assert!(x >= 1);
+        let mut res: Vec<(usize, u32)> = vec![];
+        while x > 1 {
+            let p = self.spf[x] as usize;
+            let mut e = 0;
+            while x % p == 0 {
+                x /= p;
+                e += 1;
+            }
+            res.push((p, e));
+        }
+        res
+    }
+
+    /// Values `g(0..=max())` of the multiplicative function with
+    /// `g(1) = one` and `g(p^e) = f(p, e)`. `g(0)` is `T::default()`.
+    #[allow(clippy::manual_is_multiple_of)]
+    pub fn multiplicative<T>(&self, one: T, mut f: impl FnMut(usize, u32) -> T) -> Vec<T>
+    where
+        T: Clone + Default + ops::Mul<Output = T>,
+    {
+        let n = self.max();
+        let mut res = vec![T::default(); n + 1];
+        if n == 0 {
+            return res;
+        }
+        res[1] = one;
+        // The largest power of spf(i) dividing i, and its exponent.
+        let mut pk = vec![1usize; n + 1];
+        let mut exp = vec![0u32; n + 1];
+        for i in 2..=n {
+            let p = self.spf[i] as usize;
+            let q = i / p;
+            if q % p == 0 {
+                pk[i] = pk[q] * p;
+                exp[i] = exp[q] + 1;
+            } else {
+                pk[i] = p;
+                exp[i] = 1;
+            }
+            res[i] = if pk[i] == i {
+                f(p, exp[i])
+            } else {
+                res[i / pk[i]].clone() * res[pk[i]].clone()
+            };
+        }
+        res
+    }
+
+    pub fn mobius(&self) -> Vec<i32> {
+        self.multiplicative(1, |_, e| if e == 1 { -1 } else { 0 })
+    }
+
+    pub fn euler_phi(&self) -> Vec<u64> {
+        self.multiplicative(1, |p, e| {
+            let p = p as u64;
+            p.pow(e - 1) * (p - 1)
+        })
+    }
+
+    /// Number of divisors.
+    pub fn divisor_count(&self) -> Vec<u32> {
+        self.multiplicative(1, |_, e| e + 1)
+    }
+
+    /// Sum of divisors.
+    pub fn divisor_sum(&self) -> Vec<u64> {
+        self.multiplicative(1, |p, e| {
+            let p = p as u64;
+            (p.pow(e + 1) - 1) / (p - 1)
+        })
+    }
+}
+
+/// `sum_{p <= n, p prime} w(p)` for a completely multiplicative `w` by
+/// Lucy_Hedgehog's method in O(n^(3/4)). `s(v)` is `sum_{2 <= i <= v} w(i)`.
+fn lucy<T>(n: u64, s: impl Fn(u64) -> T, w: impl Fn(u64) -> T) -> T
+where
+    T: Copy + PartialEq + ops::Sub<Output = T> + ops::Mul<Output = T> + ops::SubAssign,
+{
+    if n < 2 {
+        return s(n);
+    }
+    let sq = UInt::isqrt(n);
+    let mut small: Vec<T> = (0..=sq).map(&s).collect();
+    let mut large: Vec<T> = (0..=sq).map(|k| s(n / k.max(1))).collect();
+    for p in 2..=sq {
+        if small[p as usize] == small[p as usize - 1] {
+            continue;
+        }
+        let sp = small[p as usize - 1];
+        let wp = w(p);
+        let p2 = p * p;
+        for k in 1..=sq.min(n / p2) {
+            let kp = k * p;
+            let v = if kp <= sq {
+                large[kp as usize]
+            } else {
+                small[(n / kp) as usize]
+            };
+            large[k as usize] -= wp * (v - sp);
+        }
+        for v in (p2..=sq).rev() {
+            let d = small[(v / p) as usize] - sp;
+            small[v as usize] -= wp * d;
+        }
+    }
+    large[1]
+}
+
+/// Number of primes at most `n`, in O(n^(3/4)).
+pub fn prime_count(n: u64) -> u64 {
+    lucy(n, |v| v.saturating_sub(1), |_| 1)
+}
+
+/// Sum of the primes at most `n`, in O(n^(3/4)).
+pub fn prime_sum(n: u64) -> u128 {
+    lucy(
+        n,
+        |v| {
+            let v = v as u128;
+            (v * (v + 1) / 2).saturating_sub(1)
+        },
+        |p| p as u128,
+    )
+}
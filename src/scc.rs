@@ -1,3 +1,5 @@
+use crate::graph2::IndexedGraph;
+
 pub fn scc<A, I>(num_verts: usize, adj: A) -> (usize, Vec<usize>)
 where
     A: FnMut(usize) -> I,
@@ -62,3 +64,37 @@ where
         }
     }
 }
+
+/// `scc` on the neighbors of an `IndexedGraph`.
+pub fn scc_graph<G: IndexedGraph + ?Sized>(g: &G) -> (usize, Vec<usize>) {
+    scc(g.num_nodes(), |u| g.neighbors(u))
+}
+
+/// Components as returned by `scc` together with the condensation DAG.
+/// Component ids are in topological order, and `dag[c]` lists the
+/// successors of `c` without duplicates or self-loops, in increasing order.
+pub fn condense<A, I>(num_verts: usize, mut adj: A) -> (Vec<usize>, Vec<Vec<usize>>)
+where
+    A: FnMut(usize) -> I,
+    I: IntoIterator<Item = usize>,
+{
+    let (count, comp) = scc(num_verts, &mut adj);
+    let mut dag = vec![vec![]; count];
+    for u in 0..num_verts {
+        for v in adj(u) {
+            if comp[u] != comp[v] {
+                dag[comp[u]].push(comp[v]);
+            }
+        }
+    }
+    for succ in &mut dag {
+        succ.sort_unstable();
+        succ.dedup();
+    }
+    (comp, dag)
+}
+
+/// `condense` on the neighbors of an `IndexedGraph`.
+pub fn condense_graph<G: IndexedGraph + ?Sized>(g: &G) -> (Vec<usize>, Vec<Vec<usize>>) {
+    condense(g.num_nodes(), |u| g.neighbors(u))
+}
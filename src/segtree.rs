@@ -1,9 +1,6 @@
 use std::{mem, ops::Deref};
 
-pub trait Monoid {
-    fn id() -> Self;
-    fn op(&self, other: &Self) -> Self;
-}
+pub use crate::algebra::Monoid;
 
 #[derive(Clone, Debug)]
 pub struct SegTree<T>(Vec<T>);
@@ -67,6 +64,86 @@ impl<T: Monoid> SegTree<T> {
         orig
     }
 
+    /// Largest `r` such that `pred(sum(l, r))` holds, assuming `pred` is
+    /// monotone and `pred(T::id())`.
+    pub fn max_right<P: FnMut(&T) -> bool>(&self, l: usize, mut pred: P) -> usize {
+        assert!(l <= self.len());
+        debug_assert!(pred(&T::id()));
+        let n = self.len();
+        let mut sum = T::id();
+        for mut i in self.nodes(l, n) {
+            let x = sum.op(&self.0[i]);
+            if pred(&x) {
+                sum = x;
+                continue;
+            }
+            while i < n {
+                i *= 2;
+                let x = sum.op(&self.0[i]);
+                if pred(&x) {
+                    sum = x;
+                    i += 1;
+                }
+            }
+            return i - n;
+        }
+        n
+    }
+
+    /// Smallest `l` such that `pred(sum(l, r))` holds, assuming `pred` is
+    /// monotone and `pred(T::id())`.
+    pub fn min_left<P: FnMut(&T) -> bool>(&self, r: usize, mut pred: P) -> usize {
+        assert!(r <= self.len());
+        debug_assert!(pred(&T::id()));
+        let n = self.len();
+        let mut sum = T::id();
+        for mut i in self.nodes(0, r).into_iter().rev() {
+            let x = self.0[i].op(&sum);
+            if pred(&x) {
+                sum = x;
+                continue;
+            }
+            while i < n {
+                i = 2 * i + 1;
+                let x = self.0[i].op(&sum);
+                if pred(&x) {
+                    sum = x;
+                    i -= 1;
+                }
+            }
+            return i + 1 - n;
+        }
+        0
+    }
+
+    /// Nodes covering `[l, r)`, from left to right.
+    fn nodes(&self, l: usize, r: usize) -> Vec<usize> {
+        let mut left = vec![];
+        let mut right = vec![];
+        if l < r {
+            let mut l = l + self.len();
+            l >>= l.trailing_zeros();
+            let mut r = r + self.len();
+            r >>= r.trailing_zeros();
+            loop {
+                if l >= r {
+                    left.push(l);
+                    l += 1;
+                    l >>= l.trailing_zeros();
+                } else {
+                    r -= 1;
+                    right.push(r);
+                    r >>= r.trailing_zeros();
+                }
+                if l == r {
+                    break;
+                }
+            }
+        }
+        left.extend(right.into_iter().rev());
+        left
+    }
+
     pub fn clear(&mut self) {
         for a in &mut self.0 {
             *a = T::id();
@@ -84,6 +161,9 @@ impl<T> Deref for SegTree<T> {
 impl<T: Monoid> From<Vec<T>> for SegTree<T> {
     fn from(mut a: Vec<T>) -> Self {
         let len = a.len();
+        if len == 0 {
+            return Self(a);
+        }
         a.reserve(len);
         let ptr = a.as_mut_ptr();
         unsafe {
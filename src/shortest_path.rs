@@ -0,0 +1,307 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, VecDeque},
+    ops::{Add, Sub},
+};
+
+use crate::{bounded::Bounded, dijkstra::Zero, graph2::WeightedGraph};
+
+const NIL: usize = !0;
+
+/// Edge weights and distances. `max_value()` stands for unreachable.
+pub trait Weight: Copy + Ord + Zero + Bounded + Add<Output = Self> + Sub<Output = Self> {}
+
+impl<T: Copy + Ord + Zero + Bounded + Add<Output = T> + Sub<Output = T>> Weight for T {}
+
+/// Distances from a single source with the shortest path tree.
+#[derive(Clone, Debug)]
+pub struct ShortestPaths<W> {
+    pub dist: Vec<W>,
+    /// Predecessor on a shortest path, `!0` for the source and unreachable
+    /// nodes.
+    pub prev: Vec<usize>,
+}
+
+impl<W: Weight> ShortestPaths<W> {
+    fn new(n: usize, s: usize) -> Self {
+        let mut dist = vec![W::max_value(); n];
+        dist[s] = W::zero();
+        Self {
+            dist,
+            prev: vec![NIL; n],
+        }
+    }
+
+    pub fn is_reachable(&self, t: usize) -> bool {
+        self.dist[t] != W::max_value()
+    }
+
+    /// Nodes on a shortest path from the source to `t`.
+    pub fn path(&self, t: usize) -> Option<Vec<usize>> {
+        if !self.is_reachable(t) {
+            return None;
+        }
+        let mut path = vec![t];
+        let mut v = t;
+        while self.prev[v] != NIL {
+            v = self.prev[v];
+            path.push(v);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    #[inline]
+    fn relax(&mut self, u: usize, v: usize, w: W) -> bool {
+        let d = self.dist[u] + w;
+        if d < self.dist[v] {
+            self.dist[v] = d;
+            self.prev[v] = u;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Dijkstra with a binary heap in O(m log n). Weights must be nonnegative.
+pub fn dijkstra<W: Weight, G: WeightedGraph<W> + ?Sized>(g: &G, s: usize) -> ShortestPaths<W> {
+    let mut sp = ShortestPaths::new(g.num_nodes(), s);
+    let mut heap = BinaryHeap::new();
+    heap.push(Reverse((W::zero(), s)));
+    while let Some(Reverse((d, u))) = heap.pop() {
+        if d > sp.dist[u] {
+            continue;
+        }
+        for (v, w) in g.edges(u) {
+            if sp.relax(u, v, w) {
+                heap.push(Reverse((sp.dist[v], v)));
+            }
+        }
+    }
+    sp
+}
+
+/// Monotone priority queue on `u64` keys. Each pop is amortized O(log C)
+/// for keys up to `C`.
+struct RadixHeap<T> {
+    last: u64,
+    len: usize,
+    buckets: Vec<Vec<(u64, T)>>,
+}
+
+impl<T> RadixHeap<T> {
+    fn new() -> Self {
+        Self {
+            last: 0,
+            len: 0,
+            buckets: (0..65).map(|_| vec![]).collect(),
+        }
+    }
+
+    #[inline]
+    fn bucket(&self, key: u64) -> usize {
+        (u64::BITS - (key ^ self.last).leading_zeros()) as usize
+    }
+
+    fn push(&mut self, key: u64, val: T) {
+        debug_assert!(key >= self.last);
+        let i = self.bucket(key);
+        self.buckets[i].push((key, val));
+        self.len += 1;
+    }
+
+    fn pop(&mut self) -> Option<(u64, T)> {
+        if self.len == 0 {
+            return None;
+        }
+        if self.buckets[0].is_empty() {
+            let i = self.buckets.iter().position(|b| !b.is_empty()).unwrap();
+            let bucket = std::mem::take(&mut self.buckets[i]);
+            self.last = bucket.iter().map(|&(k, _)| k).min().unwrap();
+            for (k, v) in bucket {
+                let j = self.bucket(k);
+                self.buckets[j].push((k, v));
+            }
+        }
+        self.len -= 1;
+        self.buckets[0].pop()
+    }
+}
+
+/// Dijkstra with a radix heap, for integer weights.
+pub fn dijkstra_radix<W, G>(g: &G, s: usize) -> ShortestPaths<W>
+where
+    W: Weight + Into<u64>,
+    G: WeightedGraph<W> + ?Sized,
+{
+    let mut sp = ShortestPaths::<W>::new(g.num_nodes(), s);
+    let mut heap = RadixHeap::new();
+    heap.push(0, s);
+    while let Some((d, u)) = heap.pop() {
+        if d > sp.dist[u].into() {
+            continue;
+        }
+        for (v, w) in g.edges(u) {
+            if sp.relax(u, v, w) {
+                heap.push(sp.dist[v].into(), v);
+            }
+        }
+    }
+    sp
+}
+
+/// BFS with a deque for graphs whose weights are all either zero or one
+/// common positive value.
+pub fn bfs01<W: Weight, G: WeightedGraph<W> + ?Sized>(g: &G, s: usize) -> ShortestPaths<W> {
+    let mut sp = ShortestPaths::new(g.num_nodes(), s);
+    let mut done = vec![false; g.num_nodes()];
+    let mut que = VecDeque::new();
+    que.push_back(s);
+    while let Some(u) = que.pop_front() {
+        if std::mem::replace(&mut done[u], true) {
+            continue;
+        }
+        for (v, w) in g.edges(u) {
+            if sp.relax(u, v, w) {
+                if w == W::zero() {
+                    que.push_front(v);
+                } else {
+                    que.push_back(v);
+                }
+            }
+        }
+    }
+    sp
+}
+
+/// Bellman-Ford in O(nm). `None` if a negative cycle is reachable from `s`.
+pub fn bellman_ford<W: Weight, G: WeightedGraph<W> + ?Sized>(
+    g: &G,
+    s: usize,
+) -> Option<ShortestPaths<W>> {
+    let n = g.num_nodes();
+    let mut sp = ShortestPaths::new(n, s);
+    for i in 0..n {
+        let mut updated = false;
+        for u in 0..n {
+            if !sp.is_reachable(u) {
+                continue;
+            }
+            for (v, w) in g.edges(u) {
+                updated |= sp.relax(u, v, w);
+            }
+        }
+        if !updated {
+            return Some(sp);
+        }
+        if i == n - 1 {
+            return None;
+        }
+    }
+    Some(sp)
+}
+
+/// Bellman-Ford with a queue (SPFA). Usually much faster than
+/// `bellman_ford`, with the same O(nm) worst case. `None` if a negative cycle
+/// is reachable from `s`.
+pub fn spfa<W: Weight, G: WeightedGraph<W> + ?Sized>(g: &G, s: usize) -> Option<ShortestPaths<W>> {
+    let n = g.num_nodes();
+    let mut sp = ShortestPaths::new(n, s);
+    let mut in_queue = vec![false; n];
+    // Number of edges on the current path to each node.
+    let mut len = vec![0; n];
+    let mut que = VecDeque::new();
+    que.push_back(s);
+    in_queue[s] = true;
+    while let Some(u) = que.pop_front() {
+        in_queue[u] = false;
+        for (v, w) in g.edges(u) {
+            if sp.relax(u, v, w) {
+                len[v] = len[u] + 1;
+                if len[v] >= n {
+                    return None;
+                }
+                if !in_queue[v] {
+                    in_queue[v] = true;
+                    que.push_back(v);
+                }
+            }
+        }
+    }
+    Some(sp)
+}
+
+/// All-pairs distances in O(n^3), `max_value()` for unreachable pairs.
+/// `None` if there is a negative cycle.
+pub fn floyd_warshall<W: Weight, G: WeightedGraph<W> + ?Sized>(g: &G) -> Option<Vec<Vec<W>>> {
+    let n = g.num_nodes();
+    let inf = W::max_value();
+    let mut dist = vec![vec![inf; n]; n];
+    for (u, row) in dist.iter_mut().enumerate() {
+        row[u] = W::zero();
+        for (v, w) in g.edges(u) {
+            row[v] = row[v].min(w);
+        }
+    }
+    for k in 0..n {
+        let dk = dist[k].clone();
+        for row in &mut dist {
+            let dik = row[k];
+            if dik == inf {
+                continue;
+            }
+            for (d, &dkj) in row.iter_mut().zip(&dk) {
+                if dkj != inf && dik + dkj < *d {
+                    *d = dik + dkj;
+                }
+            }
+        }
+        // Stop at the first negative cycle, before the distances around it
+        // keep doubling and overflow.
+        if (0..n).any(|u| dist[u][u] < W::zero()) {
+            return None;
+        }
+    }
+    Some(dist)
+}
+
+/// All-pairs shortest paths by Johnson's algorithm in O(nm log n), for graphs
+/// that may have negative edges. `None` if there is a negative cycle.
+pub fn johnson<W: Weight, G: WeightedGraph<W> + ?Sized>(g: &G) -> Option<Vec<ShortestPaths<W>>> {
+    let n = g.num_nodes();
+    // Potentials from a virtual source joined to every node with weight 0.
+    let mut h = vec![W::zero(); n];
+    for i in 0..=n {
+        let mut updated = false;
+        for u in 0..n {
+            for (v, w) in g.edges(u) {
+                if h[u] + w < h[v] {
+                    h[v] = h[u] + w;
+                    updated = true;
+                }
+            }
+        }
+        if !updated {
+            break;
+        }
+        if i == n {
+            return None;
+        }
+    }
+    let reweighted: Vec<Vec<(usize, W)>> = (0..n)
+        .map(|u| g.edges(u).map(|(v, w)| (v, w + h[u] - h[v])).collect())
+        .collect();
+    let res = (0..n)
+        .map(|s| {
+            let mut sp = dijkstra(&reweighted[..], s);
+            for (v, d) in sp.dist.iter_mut().enumerate() {
+                if *d != W::max_value() {
+                    *d = *d + h[v] - h[s];
+                }
+            }
+            sp
+        })
+        .collect();
+    Some(res)
+}
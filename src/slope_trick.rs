@@ -0,0 +1,147 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use crate::cht::Num;
+
+/// Convex piecewise linear function with integer breakpoints.
+///
+/// `l` holds the breakpoints left of the minimum and `r` those right of it,
+/// each shifted by a lazy offset.
+#[derive(Clone, Debug)]
+pub struct SlopeTrick<T> {
+    min: T,
+    l: BinaryHeap<T>,
+    r: BinaryHeap<Reverse<T>>,
+    add_l: T,
+    add_r: T,
+}
+
+impl<T: Num + Ord + Default> SlopeTrick<T> {
+    /// `f(x) = 0`
+    pub fn new() -> Self {
+        Self {
+            min: T::default(),
+            l: BinaryHeap::new(),
+            r: BinaryHeap::new(),
+            add_l: T::default(),
+            add_r: T::default(),
+        }
+    }
+
+    pub fn min(&self) -> T {
+        self.min
+    }
+
+    /// Closed range of `x` that attains the minimum. `None` is unbounded.
+    pub fn argmin(&self) -> (Option<T>, Option<T>) {
+        (self.top_l(), self.top_r())
+    }
+
+    fn top_l(&self) -> Option<T> {
+        self.l.peek().map(|&a| a + self.add_l)
+    }
+
+    fn top_r(&self) -> Option<T> {
+        self.r.peek().map(|&Reverse(a)| a + self.add_r)
+    }
+
+    fn push_l(&mut self, a: T) {
+        self.l.push(a - self.add_l);
+    }
+
+    fn push_r(&mut self, a: T) {
+        self.r.push(Reverse(a - self.add_r));
+    }
+
+    fn pop_l(&mut self) -> T {
+        self.l.pop().unwrap() + self.add_l
+    }
+
+    fn pop_r(&mut self) -> T {
+        self.r.pop().unwrap().0 + self.add_r
+    }
+
+    pub fn add_const(&mut self, c: T) -> &mut Self {
+        self.min = self.min + c;
+        self
+    }
+
+    /// `f(x) += max(0, x - a)`
+    pub fn add_x_minus_a(&mut self, a: T) -> &mut Self {
+        if let Some(l) = self.top_l() {
+            if l > a {
+                self.min = self.min + (l - a);
+            }
+        }
+        self.push_l(a);
+        let l = self.pop_l();
+        self.push_r(l);
+        self
+    }
+
+    /// `f(x) += max(0, a - x)`
+    pub fn add_a_minus_x(&mut self, a: T) -> &mut Self {
+        if let Some(r) = self.top_r() {
+            if a > r {
+                self.min = self.min + (a - r);
+            }
+        }
+        self.push_r(a);
+        let r = self.pop_r();
+        self.push_l(r);
+        self
+    }
+
+    /// `f(x) += |x - a|`
+    pub fn add_abs(&mut self, a: T) -> &mut Self {
+        self.add_x_minus_a(a).add_a_minus_x(a)
+    }
+
+    /// `f(x) = min_{y <= x} f(y)`
+    pub fn prefix_min(&mut self) -> &mut Self {
+        self.r.clear();
+        self
+    }
+
+    /// `f(x) = min_{y >= x} f(y)`
+    pub fn suffix_min(&mut self) -> &mut Self {
+        self.l.clear();
+        self
+    }
+
+    /// `f(x) = min_{x - b <= y <= x - a} f(y)`
+    pub fn sliding_window_min(&mut self, a: T, b: T) -> &mut Self {
+        assert!(a <= b);
+        self.add_l = self.add_l + a;
+        self.add_r = self.add_r + b;
+        self
+    }
+
+    /// `f(x) = f(x - a)`
+    pub fn translate(&mut self, a: T) -> &mut Self {
+        self.sliding_window_min(a, a)
+    }
+
+    /// Evaluates `f(x)` in O(n).
+    pub fn eval(&self, x: T) -> T {
+        let mut y = self.min;
+        for &l in &self.l {
+            let l = l + self.add_l;
+            if l > x {
+                y = y + (l - x);
+            }
+        }
+        for &Reverse(r) in &self.r {
+            let r = r + self.add_r;
+            if x > r {
+                y = y + (x - r);
+            }
+        }
+        y
+    }
+}
+
+impl<T: Num + Ord + Default> Default for SlopeTrick<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,17 +1,40 @@
+mod algebra;
 mod bits;
 mod d2;
 mod d3;
+mod disjoint_sparse_table;
+mod dft2;
 mod dsu;
+mod dynamic_segtree;
 mod experimental;
 mod f2;
+mod fact;
+mod factorize;
+mod fenwick_tree;
 mod float;
+mod graph;
+mod hld;
 mod input;
 mod input2;
 mod int;
 mod kmp;
+mod lazy_seg_tree;
+mod lca;
 mod light_vec;
+mod linear_recurrence;
+mod lowlink;
+mod mo;
+mod mod_solve;
+mod modint64;
 mod modint_poly;
+mod montgomery;
+mod mst;
+mod persistent;
+mod primes;
 mod rational;
 mod rbstree;
+mod scc;
 mod segtree;
+mod shortest_path;
+mod slope_trick;
 mod trie;
@@ -0,0 +1,41 @@
+use crate::algebra::*;
+use crate::fenwick_tree::FenwickTree;
+use crate::lazy_seg_tree::LazySegTree;
+use crate::modint2::*;
+use crate::random::*;
+use crate::segtree::SegTree;
+
+#[test]
+fn one_type_all_trees() {
+    type T = (Sum<i64>, Min<i64>, Max<i64>, Xor<u32>);
+    let mut rng = Xoshiro::seed_from_u64(1);
+    let n = 20;
+    let a: Vec<i64> = (0..n).map(|_| rng.range(-100, 100)).collect();
+    let elem = |x: i64| -> T { (Sum(x), Min(x), Max(x), Xor(x as u32)) };
+    let st: SegTree<T> = a.iter().map(|&x| elem(x)).collect();
+    let mut lst: LazySegTree<T, ()> = a.iter().map(|&x| elem(x)).collect();
+    let mut ft = FenwickTree::<(Sum<i64>, Xor<u32>)>::new(n);
+    for (i, &x) in a.iter().enumerate() {
+        ft.add(i, (Sum(x), Xor(x as u32)));
+    }
+    for l in 0..=n {
+        for r in l..=n {
+            let naive = a[l..r].iter().fold(T::id(), |acc, &x| acc.op(&elem(x)));
+            assert_eq!(st.sum(l, r), naive);
+            assert_eq!(lst.prod(l, r), naive);
+            assert_eq!(ft.sum_range(l, r), (naive.0, naive.3));
+        }
+    }
+}
+
+#[test]
+fn affine_action() {
+    type M = ConstMod<998244353>;
+    let f = Affine::<M>::new(mint(2), mint(3));
+    let g = Affine::<M>::new(mint(5), mint(7));
+    let x = mint(11);
+    assert_eq!(f.op(&g).apply(x), f.apply(g.apply(x)));
+    assert_eq!(f.op(&Affine::id()), f);
+    let s = (Sum(x + x), Sum(mint(2)));
+    assert_eq!(f.act(&s), (Sum(f.apply(x) + f.apply(x)), Sum(mint(2))));
+}
@@ -0,0 +1,93 @@
+use crate::dft2::*;
+use crate::int::crt;
+use crate::modint2::*;
+use crate::modint_poly::Poly;
+use crate::random::*;
+
+fn naive_i128(a: &[i64], b: &[i64]) -> Vec<i128> {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+    let mut c = vec![0; a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        for (j, &y) in b.iter().enumerate() {
+            c[i + j] += x as i128 * y as i128;
+        }
+    }
+    c
+}
+
+#[test]
+fn any_mod_random() {
+    let mut rng = Xoshiro::seed_from_u64(1);
+    for m in [2, 3, 1_000_000_007, 998244353, u32::MAX] {
+        for _ in 0..10 {
+            let n = rng.range(0, 50);
+            let k = rng.range(0, 50);
+            let a: Vec<u32> = (0..n).map(|_| rng.range(0, m)).collect();
+            let b: Vec<u32> = (0..k).map(|_| rng.range(0, m)).collect();
+            let ai: Vec<i64> = a.iter().map(|&x| x as i64).collect();
+            let bi: Vec<i64> = b.iter().map(|&x| x as i64).collect();
+            let naive: Vec<u32> = naive_i128(&ai, &bi)
+                .into_iter()
+                .map(|x| (x % m as i128) as u32)
+                .collect();
+            assert_eq!(convolution_any_mod(&a, &b, m), naive);
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "too long")]
+fn any_mod_beyond_exact_bound() {
+    let m = 4294967291;
+    let a = vec![m - 1; 1 << 22];
+    convolution_any_mod(&a, &a, m);
+}
+
+#[test]
+fn var_mod() {
+    set_var_mod(1_000_000_007);
+    let a: Vec<VarMint> = (1..=100u32).map(var_mint).collect();
+    let c = convolution_mod(&a, &a);
+    assert_eq!(c.len(), 199);
+    for (i, x) in c.iter().enumerate() {
+        let naive: u64 = (0..=i)
+            .filter(|&j| j < 100 && i - j < 100)
+            .map(|j| (j as u64 + 1) * (i - j + 1) as u64)
+            .sum::<u64>()
+            % 1_000_000_007;
+        assert_eq!(x.get() as u64, naive, "{i}");
+    }
+    let f: Poly<VarMod> = a.iter().copied().collect();
+    assert_eq!((&f * &f).to_vec(), c);
+}
+
+#[test]
+fn exact_random() {
+    let mut rng = Xoshiro::seed_from_u64(2);
+    for _ in 0..20 {
+        let n = rng.range(1, 40);
+        let k = rng.range(1, 40);
+        let a: Vec<i64> = (0..n).map(|_| rng.range(-1 << 40, 1 << 40)).collect();
+        let b: Vec<i64> = (0..k).map(|_| rng.range(-1 << 40, 1 << 40)).collect();
+        let naive = naive_i128(&a, &b);
+        assert_eq!(convolution_i128(&a, &b), naive);
+        let au: Vec<u64> = a.iter().map(|&x| x.unsigned_abs()).collect();
+        let bu: Vec<u64> = b.iter().map(|&x| x.unsigned_abs()).collect();
+        let ai: Vec<i64> = au.iter().map(|&x| x as i64).collect();
+        let bi: Vec<i64> = bu.iter().map(|&x| x as i64).collect();
+        let naive: Vec<u64> = naive_i128(&ai, &bi).into_iter().map(|x| x as u64).collect();
+        assert_eq!(convolution_u64(&au, &bu), naive);
+    }
+}
+
+#[test]
+fn crt_agrees() {
+    let mut rng = Xoshiro::seed_from_u64(3);
+    for _ in 0..100 {
+        let x: i64 = rng.range(0, 754974721 * 167772161);
+        let (y, m) = crt(x % 754974721, 754974721, x % 167772161, 167772161).unwrap();
+        assert_eq!((y, m), (x, 754974721 * 167772161));
+    }
+}
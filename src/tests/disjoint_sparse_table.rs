@@ -0,0 +1,58 @@
+use crate::disjoint_sparse_table::*;
+use crate::modint2::*;
+use crate::random::*;
+use crate::segtree::Monoid;
+
+#[test]
+fn concat() {
+    for n in 0..40 {
+        let a: Vec<String> = (0..n).map(|i| format!("{i},")).collect();
+        let dst = DisjointSparseTable::new(a.clone(), |x: &String, y: &String| x.clone() + y);
+        for l in 0..=n {
+            for r in l..=n {
+                let naive = a[l..r].concat();
+                if l == r {
+                    assert_eq!(dst.try_query(l..r), None);
+                } else {
+                    assert_eq!(dst.query(l..r), naive);
+                    assert_eq!(dst.query(l..=r - 1), naive);
+                }
+            }
+        }
+        if n > 0 {
+            assert_eq!(dst.query(..), a.concat());
+        }
+    }
+}
+
+const M: u32 = 998244353;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct Affine(Mint<M>, Mint<M>);
+
+impl Monoid for Affine {
+    fn id() -> Self {
+        Affine(mint(1), mint(0))
+    }
+    fn op(&self, other: &Self) -> Self {
+        Affine(self.0 * other.0, self.0 * other.1 + self.1)
+    }
+}
+
+#[test]
+fn affine_monoid() {
+    let mut rng = Xoshiro::seed_from_u64(1);
+    for n in [1, 2, 3, 7, 8, 9, 100] {
+        let a: Vec<Affine> = (0..n)
+            .map(|_| Affine(mint(rng.range(0, M)), mint(rng.range(0, M))))
+            .collect();
+        let dst: DisjointSparseTable<_, _> = a.iter().copied().collect();
+        for _ in 0..500 {
+            let l = rng.range_inclusive(0, n);
+            let r = rng.range_inclusive(0, n);
+            let (l, r) = (l.min(r), l.max(r));
+            let naive = a[l..r].iter().fold(Affine::id(), |x, y| x.op(y));
+            assert_eq!(dst.prod(l..r), naive);
+        }
+    }
+}
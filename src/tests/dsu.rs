@@ -26,3 +26,90 @@ fn dsu_merge() {
     assert_eq!(dsu.data(1), "12");
     assert_eq!(dsu.data(2), "12");
 }
+
+#[test]
+fn rollback_dsu() {
+    let mut dsu = RollbackDsu::new(5);
+    dsu.unite(0, 1);
+    let s = dsu.snapshot();
+    assert!(dsu.unite(1, 2).is_modified());
+    assert!(!dsu.unite(0, 2).is_modified());
+    dsu.unite(3, 4);
+    assert_eq!(dsu.count(), 2);
+    assert_eq!(dsu.size(2), 3);
+    dsu.rollback(s);
+    assert_eq!(dsu.count(), 4);
+    assert!(dsu.is_same(0, 1));
+    assert!(!dsu.is_same(1, 2));
+    assert!(!dsu.is_same(3, 4));
+    assert_eq!(dsu.size(0), 2);
+    dsu.rollback(0);
+    assert_eq!(dsu.count(), 5);
+}
+
+#[test]
+fn weighted_dsu() {
+    use crate::algebra::Sum;
+    let mut dsu = WeightedDsu::new(5);
+    assert!(dsu.unite(0, 1, Sum(3)).is_some());
+    assert!(dsu.unite(2, 1, Sum(-2)).is_some());
+    assert!(dsu.unite(3, 4, Sum(10)).is_some());
+    assert_eq!(dsu.diff(0, 2), Some(Sum(5)));
+    assert_eq!(dsu.diff(2, 0), Some(Sum(-5)));
+    assert_eq!(dsu.diff(0, 3), None);
+    assert!(dsu.unite(0, 2, Sum(4)).is_none());
+    assert!(dsu.unite(0, 2, Sum(5)).is_some());
+    assert!(dsu.unite(4, 2, Sum(1)).is_some());
+    assert_eq!(dsu.diff(3, 0), Some(Sum(6)));
+    assert_eq!(dsu.size(0), 5);
+}
+
+#[test]
+fn offline_dynamic_connectivity() {
+    use crate::random::*;
+    let n = 8;
+    let mut rng = Xoshiro::seed_from_u64(1);
+    let mut dc = OfflineDynamicConnectivity::new(n);
+    let mut edges: Vec<(usize, usize)> = vec![];
+    let mut expected = vec![];
+    for _ in 0..300 {
+        match rng.range(0, 3) {
+            0 => {
+                let u = rng.range(0, n);
+                let v = rng.range(0, n);
+                dc.add_edge(u, v);
+                edges.push((u, v));
+            }
+            1 if !edges.is_empty() => {
+                let i = rng.range(0, edges.len());
+                let (u, v) = edges.swap_remove(i);
+                dc.remove_edge(v, u);
+            }
+            _ => {
+                let mut naive = Dsu::new(n);
+                for &(u, v) in &edges {
+                    naive.unite(u, v);
+                }
+                assert_eq!(dc.query(), expected.len());
+                let roots: Vec<usize> = (0..n).map(|u| naive.root(u)).collect();
+                expected.push(roots);
+            }
+        }
+    }
+    let mut i = 0;
+    dc.run(|q, dsu| {
+        assert_eq!(q, i);
+        for u in 0..n {
+            for v in 0..n {
+                assert_eq!(dsu.is_same(u, v), expected[q][u] == expected[q][v]);
+            }
+        }
+        assert_eq!(dsu.count(), naive_count(&expected[q]));
+        i += 1;
+    });
+    assert_eq!(i, expected.len());
+}
+
+fn naive_count(roots: &[usize]) -> usize {
+    (0..roots.len()).filter(|&u| roots[u] == u).count()
+}
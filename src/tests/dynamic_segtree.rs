@@ -0,0 +1,198 @@
+use crate::algebra::*;
+use crate::dynamic_segtree::*;
+use crate::modint2::*;
+use crate::random::*;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct Add(i64);
+
+impl Monoid for Add {
+    fn id() -> Self {
+        Add(0)
+    }
+    fn op(&self, other: &Self) -> Self {
+        Add(self.0 + other.0)
+    }
+}
+
+impl Action<(Sum<i64>, Sum<i64>)> for Add {
+    fn act(&self, x: &(Sum<i64>, Sum<i64>)) -> (Sum<i64>, Sum<i64>) {
+        (Sum(x.0 .0 + self.0 * x.1 .0), x.1)
+    }
+}
+
+const BASE: i64 = -123_456_789_012;
+const N: usize = 30;
+
+fn random_range(rng: &mut Xoshiro) -> (usize, usize) {
+    let l = rng.range_inclusive(0, N);
+    let r = rng.range_inclusive(0, N);
+    (l.min(r), l.max(r))
+}
+
+#[test]
+fn point_set_random() {
+    let mut rng = Xoshiro::seed_from_u64(1);
+    let mut st = DynamicSegTree::<Min<i64>>::new(-1 << 60, 1 << 60);
+    let mut naive = vec![i64::MAX; N];
+    for _ in 0..1000 {
+        if rng.range(0, 2) == 0 {
+            let i = rng.range(0, N);
+            let x = rng.range(-100, 100);
+            st.set(BASE + i as i64, Min(x));
+            naive[i] = x;
+        } else {
+            let (l, r) = random_range(&mut rng);
+            let naive_min = naive[l..r].iter().copied().min().unwrap_or(i64::MAX);
+            assert_eq!(st.prod(BASE + l as i64, BASE + r as i64).0, naive_min);
+        }
+    }
+    assert_eq!(st.versions(), 1);
+    assert_eq!(st.prod(-1 << 60, BASE).0, i64::MAX);
+}
+
+#[test]
+fn range_add_random() {
+    let mut rng = Xoshiro::seed_from_u64(2);
+    let mut st = DynamicSegTree::<_, Add>::with_init(-1 << 60, 1 << 60, (Sum(0), Sum(1)));
+    let mut naive = vec![0; N];
+    for _ in 0..1000 {
+        let (l, r) = random_range(&mut rng);
+        match rng.range(0, 3) {
+            0 => {
+                let x = rng.range(-10, 10);
+                st.apply(BASE + l as i64, BASE + r as i64, &Add(x));
+                for y in &mut naive[l..r] {
+                    *y += x;
+                }
+            }
+            1 => {
+                let x = rng.range(-10, 10);
+                st.set(BASE + l as i64, (Sum(x), Sum(1)));
+                if l < N {
+                    naive[l] = x;
+                }
+            }
+            _ => {
+                let s = st.prod(BASE + l as i64, BASE + r as i64);
+                assert_eq!(s.0 .0, naive[l..r].iter().sum::<i64>());
+                assert_eq!(s.1 .0, (r - l) as i64);
+            }
+        }
+    }
+}
+
+#[test]
+fn whole_range() {
+    let mut st = DynamicSegTree::<_, Add>::with_init(-1 << 60, 1 << 60, (Sum(0), Sum(1)));
+    st.apply(-1 << 60, 1 << 60, &Add(3));
+    st.apply(0, 1, &Add(-3));
+    assert_eq!(
+        st.prod(-1 << 60, 1 << 60),
+        (Sum((3 << 61) - 3), Sum(1 << 61))
+    );
+    assert_eq!(st.get(0).0 .0, 0);
+    assert_eq!(st.get(-1).0 .0, 3);
+    assert!(st.num_nodes() < 200);
+}
+
+#[test]
+fn persistent_random() {
+    let mut rng = Xoshiro::seed_from_u64(3);
+    let mut st =
+        DynamicSegTree::<_, Add>::with_init(BASE, BASE + N as i64, (Sum(0), Sum(1))).persistent();
+    let mut naive = vec![vec![0; N]];
+    for _ in 0..300 {
+        let v = rng.range(0, naive.len());
+        let mut a = naive[v].clone();
+        st.branch(v);
+        let (l, r) = random_range(&mut rng);
+        let x = rng.range(-10, 10);
+        if rng.range(0, 2) == 0 {
+            st.apply(BASE + l as i64, BASE + r as i64, &Add(x));
+            for y in &mut a[l..r] {
+                *y += x;
+            }
+        } else if l < N {
+            st.update(BASE + l as i64, |&(s, c)| (Sum(s.0 + x), c));
+            a[l] += x;
+        } else {
+            st.apply(BASE, BASE, &Add(x));
+        }
+        naive.push(naive[v].clone());
+        naive.push(a);
+        assert_eq!(st.versions(), naive.len());
+        for _ in 0..5 {
+            let v = rng.range(0, naive.len());
+            let (l, r) = random_range(&mut rng);
+            let s = st.prod_at(v, BASE + l as i64, BASE + r as i64);
+            assert_eq!(s.0 .0, naive[v][l..r].iter().sum::<i64>());
+        }
+    }
+}
+
+#[test]
+fn range_affine_random() {
+    type M = ConstMod<998244353>;
+    let mut rng = Xoshiro::seed_from_u64(4);
+    let mut st = DynamicSegTree::<_, Affine<M>>::with_init(
+        -1 << 60,
+        1 << 60,
+        (Sum(mint::<998244353>(0)), Sum(mint(1))),
+    );
+    let mut naive = [mint::<998244353>(0); N];
+    st.apply(BASE, BASE + 8, &Affine::new(mint(0), mint(5)));
+    st.apply(BASE, BASE + 2, &Affine::new(mint(1), mint(1)));
+    for y in &mut naive[..8] {
+        *y = mint(5);
+    }
+    for y in &mut naive[..2] {
+        *y += mint(1);
+    }
+    assert_eq!(st.get(BASE).0 .0, mint(6));
+    for _ in 0..1000 {
+        let (l, r) = random_range(&mut rng);
+        if rng.range(0, 2) == 0 {
+            let f = Affine::new(mint(rng.range(0, 3)), mint(rng.range(0, 10)));
+            st.apply(BASE + l as i64, BASE + r as i64, &f);
+            for y in &mut naive[l..r] {
+                *y = f.apply(*y);
+            }
+        } else {
+            let s = st.prod(BASE + l as i64, BASE + r as i64);
+            let expected = naive[l..r].iter().fold(mint(0), |acc, &x| acc + x);
+            assert_eq!(s.0 .0, expected);
+        }
+    }
+}
+
+#[test]
+fn node_count() {
+    // A point update creates only the 62 nodes on its path.
+    let mut st = DynamicSegTree::<Min<i64>>::new(-1 << 60, 1 << 60);
+    st.set(BASE, Min(1));
+    assert_eq!(st.num_nodes(), 62);
+    st.set(BASE, Min(2));
+    assert_eq!(st.num_nodes(), 62);
+
+    let mut st =
+        DynamicSegTree::<_, Add>::with_init(-1 << 60, 1 << 60, (Sum(0), Sum(1))).persistent();
+    st.set(BASE, (Sum(1), Sum(1)));
+    st.set(BASE, (Sum(2), Sum(1)));
+    assert_eq!(st.num_nodes(), 2 * 62);
+    // Only the root is copied, and the next update copies each pushed
+    // sibling once.
+    st.apply(-1 << 60, 1 << 60, &Add(1));
+    assert_eq!(st.num_nodes(), 2 * 62 + 1);
+    st.set(BASE, (Sum(3), Sum(1)));
+    assert_eq!(st.num_nodes(), 3 * 62 + 1 + 61);
+    assert_eq!(st.get_at(2, BASE).0 .0, 2);
+    assert_eq!(st.get_at(3, BASE).0 .0, 3);
+    assert_eq!(st.get_at(3, BASE + 1).0 .0, 1);
+}
+
+#[test]
+#[should_panic]
+fn range_too_wide() {
+    DynamicSegTree::<Min<i64>>::new(i64::MIN, i64::MAX);
+}
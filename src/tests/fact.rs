@@ -0,0 +1,107 @@
+use crate::fact::*;
+use crate::mod_int::{self, ModInt};
+use crate::modint2::Mint;
+
+struct Mod998244353;
+
+impl mod_int::Modulo for Mod998244353 {
+    fn modulo() -> u32 {
+        998244353
+    }
+}
+
+struct Mod7;
+
+impl mod_int::Modulo for Mod7 {
+    fn modulo() -> u32 {
+        7
+    }
+}
+
+#[test]
+fn multinomial_and_catalan() {
+    let fact = Fact::<Mod998244353>::new();
+    let fixed = FixedFact::<Mod998244353>::new(20);
+    assert_eq!(fact.multinomial(&[2, 3, 4]).get(), 1260);
+    assert_eq!(fixed.multinomial(&[2, 3, 4]).get(), 1260);
+    assert_eq!(fact.multinomial(&[]).get(), 1);
+    assert_eq!(fact.multinomial(&[5, 0]).get(), 1);
+    let catalan = [1, 1, 2, 5, 14, 42, 132, 429, 1430, 4862];
+    for (n, &c) in catalan.iter().enumerate() {
+        assert_eq!(fact.catalan(n).get(), c);
+        assert_eq!(fixed.catalan(n).get(), c);
+    }
+}
+
+#[test]
+fn binom_signed() {
+    let fact = Fact::<Mod998244353>::new();
+    for n in -10i64..=10 {
+        for k in 0..10 {
+            // binom(n, k) = n (n - 1) ... (n - k + 1) / k!
+            let mut num = 1i64;
+            let mut den = 1i64;
+            for i in 0..k as i64 {
+                num *= n - i;
+                den *= i + 1;
+            }
+            let expected = ModInt::<Mod998244353>::new((num / den).rem_euclid(998244353) as u32);
+            assert_eq!(fact.binom_signed(n, k).get(), expected.get(), "{} {}", n, k);
+        }
+    }
+}
+
+#[test]
+fn binom_lucas() {
+    let fact = Fact::<Mod7>::new();
+    let fixed = FixedFact::<Mod7>::new(6);
+    let n = 200;
+    let mut pascal = vec![vec![0u32; n + 2]; n + 1];
+    for i in 0..=n {
+        pascal[i][0] = 1;
+        for k in 1..=i {
+            pascal[i][k] = (pascal[i - 1][k - 1] + pascal[i - 1][k]) % 7;
+        }
+    }
+    for (i, row) in pascal.iter().enumerate() {
+        for (k, &expected) in row[..=i + 1].iter().enumerate() {
+            assert_eq!(fact.binom_lucas(i as u64, k as u64).get(), expected);
+            assert_eq!(fixed.binom_lucas(i as u64, k as u64).get(), expected);
+        }
+    }
+    assert_eq!(fact.binom_lucas(1_000_000_000_000, 0).get(), 1);
+}
+
+type M = Mint<998244353>;
+
+#[test]
+fn stirling_rows() {
+    let n = 30;
+    let mut s1 = vec![vec![M::ZERO; n + 1]; n + 1];
+    let mut s2 = vec![vec![M::ZERO; n + 1]; n + 1];
+    s1[0][0] = M::ONE;
+    s2[0][0] = M::ONE;
+    for i in 1..=n {
+        for k in 1..=i {
+            s1[i][k] = s1[i - 1][k - 1] + M::from(i - 1) * s1[i - 1][k];
+            s2[i][k] = s2[i - 1][k - 1] + M::from(k) * s2[i - 1][k];
+        }
+    }
+    for i in 0..=n {
+        assert_eq!(stirling1_row(i), s1[i][..=i], "{}", i);
+        assert_eq!(stirling2_row(i), s2[i][..=i], "{}", i);
+    }
+}
+
+#[test]
+fn bell_and_partition() {
+    let bell = [1, 1, 2, 5, 15, 52, 203, 877, 4140, 21147, 115975];
+    let b = bell_numbers(10);
+    assert_eq!(b, bell.iter().map(|&x| M::from(x)).collect::<Vec<_>>());
+    let partition = [1, 1, 2, 3, 5, 7, 11, 15, 22, 30, 42, 56, 77, 101, 135];
+    let p = partition_numbers(14);
+    assert_eq!(p, partition.iter().map(|&x| M::from(x)).collect::<Vec<_>>());
+    assert_eq!(partition_numbers(100)[100], M::from(190569292u32));
+    assert_eq!(bell_numbers(0), vec![M::ONE]);
+    assert_eq!(partition_numbers(0), vec![M::ONE]);
+}
@@ -0,0 +1,115 @@
+use crate::factorize::*;
+use crate::random::*;
+
+#[test]
+fn is_prime_small() {
+    for n in 0..10000u64 {
+        let naive = n >= 2 && (2..n).take_while(|d| d * d <= n).all(|d| n % d != 0);
+        assert_eq!(is_prime_u64(n), naive, "{}", n);
+    }
+}
+
+#[test]
+fn is_prime_large() {
+    for p in [
+        998244353,
+        1_000_000_007,
+        (1 << 61) - 1,
+        4611686018427387847,
+        9223372036854775783,
+        18446744073709551557,
+    ] {
+        assert!(is_prime_u64(p), "{}", p);
+    }
+    for n in [
+        // Strong pseudoprimes to several small bases.
+        3215031751,
+        3825123056546413051,
+        (1 << 61) + 1,
+        998244353 * 1_000_000_007,
+        18446744073709551615,
+        4294967291 * 4294967279,
+    ] {
+        assert!(!is_prime_u64(n), "{}", n);
+    }
+}
+
+#[test]
+fn factorize_matches_naive() {
+    let mut rng = Xoshiro::seed_from_u64(1);
+    for _ in 0..1000 {
+        let n: u64 = rng.range_inclusive(1, 1 << 36);
+        assert_eq!(factorize_rho(n), factorize_naive(n).collect::<Vec<_>>());
+    }
+    for n in 1..1000 {
+        assert_eq!(factorize_rho(n), factorize_naive(n).collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn factorize_large() {
+    let cases: [(u64, &[(u64, u32)]); 5] = [
+        (
+            998244353 * 1_000_000_007,
+            &[(998244353, 1), (1_000_000_007, 1)],
+        ),
+        (4294967291 * 4294967279, &[(4294967279, 1), (4294967291, 1)]),
+        (1 << 63, &[(2, 63)]),
+        (
+            18446744073709551615,
+            &[
+                (3, 1),
+                (5, 1),
+                (17, 1),
+                (257, 1),
+                (641, 1),
+                (65537, 1),
+                (6700417, 1),
+            ],
+        ),
+        (999999999999999989, &[(999999999999999989, 1)]),
+    ];
+    for (n, f) in cases {
+        assert_eq!(factorize_rho(n), f);
+    }
+    let mut rng = Xoshiro::seed_from_u64(2);
+    for _ in 0..100 {
+        let n: u64 = rng.gen();
+        let f = factorize_rho(n.max(1));
+        assert_eq!(f.iter().fold(1, |x, &(p, e)| x * p.pow(e)), n.max(1));
+        assert!(f.windows(2).all(|w| w[0].0 < w[1].0));
+        assert!(f.iter().all(|&(p, _)| is_prime_u64(p)));
+    }
+}
+
+#[test]
+fn divisors_and_phi() {
+    for n in 1..500u64 {
+        let naive: Vec<u64> = (1..=n).filter(|d| n % d == 0).collect();
+        assert_eq!(divisors(n), naive);
+        let phi = (1..=n).filter(|&k| crate::int::gcd(k, n) == 1).count() as u64;
+        assert_eq!(euler_phi(n), phi);
+    }
+    assert_eq!(divisors(735134400).len(), 1344);
+}
+
+#[test]
+fn primitive_roots() {
+    assert_eq!(primitive_root(2), 1);
+    assert_eq!(primitive_root(998244353), 3);
+    assert_eq!(primitive_root(754974721), 11);
+    assert_eq!(primitive_root(1_000_000_007), 5);
+    for p in (3..2000).filter(|&p| is_prime_u64(p)) {
+        let g = primitive_root(p);
+        let mut x = 1;
+        let mut order = 0;
+        loop {
+            x = x * g % p;
+            order += 1;
+            if x == 1 {
+                break;
+            }
+        }
+        assert_eq!(order, p - 1);
+    }
+}
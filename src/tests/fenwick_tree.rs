@@ -0,0 +1,155 @@
+use crate::fenwick_tree::*;
+use crate::random::*;
+
+#[test]
+fn lower_bound() {
+    let mut rng = Xoshiro::seed_from_u64(1);
+    for n in 0..30 {
+        let a: Vec<i64> = (0..n).map(|_| rng.range(0, 5)).collect();
+        let mut ft = FenwickTree::new(n);
+        for (i, &x) in a.iter().enumerate() {
+            ft.add(i, Sum(x));
+        }
+        for w in 0..=a.iter().sum::<i64>() + 1 {
+            let naive = (0..n)
+                .find(|&i| a[..=i].iter().sum::<i64>() >= w)
+                .unwrap_or(n);
+            assert_eq!(ft.lower_bound(&Sum(w)), naive, "{a:?} {w}");
+        }
+    }
+}
+
+#[test]
+fn unsigned_range_sum() {
+    let mut rng = Xoshiro::seed_from_u64(5);
+    let n = 30;
+    let a: Vec<usize> = (0..n).map(|_| rng.range(0, 100)).collect();
+    let mut ft = FenwickTree::new(n);
+    let mut ft64 = FenwickTree::new(n);
+    for (i, &x) in a.iter().enumerate() {
+        ft.add(i, Sum(x));
+        ft64.add(i, Sum(x as u64));
+    }
+    for l in 0..=n {
+        for r in l..=n {
+            let naive: usize = a[l..r].iter().sum();
+            assert_eq!(ft.sum_range(l, r).0, naive);
+            assert_eq!(ft64.sum_range(l, r).0, naive as u64);
+        }
+    }
+}
+
+#[test]
+fn range_add_range_sum() {
+    let mut rng = Xoshiro::seed_from_u64(2);
+    for n in 0..20 {
+        let mut ft = RangeFenwickTree::<i64>::new(n);
+        let mut naive = vec![0; n];
+        for _ in 0..100 {
+            let l = rng.range_inclusive(0, n);
+            let r = rng.range_inclusive(0, n);
+            let (l, r) = (l.min(r), l.max(r));
+            if rng.range(0, 2) == 0 {
+                let x = rng.range(-10, 10);
+                ft.add(l, r, x);
+                for y in &mut naive[l..r] {
+                    *y += x;
+                }
+            } else {
+                assert_eq!(ft.sum_range(l, r), naive[l..r].iter().sum::<i64>());
+            }
+        }
+    }
+}
+
+#[test]
+fn range_add_unsigned() {
+    let mut rng = Xoshiro::seed_from_u64(6);
+    for n in 0..20 {
+        let mut ft = RangeFenwickTree::<u64>::new(n);
+        let mut naive = vec![0; n];
+        for _ in 0..100 {
+            let l = rng.range_inclusive(0, n);
+            let r = rng.range_inclusive(0, n);
+            let (l, r) = (l.min(r), l.max(r));
+            if rng.range(0, 2) == 0 {
+                let x = rng.range(0, 10);
+                ft.add(l, r, x);
+                for y in &mut naive[l..r] {
+                    *y += x;
+                }
+            } else {
+                assert_eq!(ft.sum_range(l, r), naive[l..r].iter().sum::<u64>());
+            }
+        }
+    }
+}
+
+#[test]
+fn dense_2d_count() {
+    let mut rng = Xoshiro::seed_from_u64(7);
+    let (h, w) = (7, 9);
+    let mut ft = FenwickTree2D::new(h, w);
+    let mut naive = vec![vec![0usize; w]; h];
+    for _ in 0..300 {
+        let i = rng.range(0, h);
+        let j = rng.range(0, w);
+        ft.add(i, j, Sum(1usize));
+        naive[i][j] += 1;
+        let i0 = rng.range_inclusive(0, h);
+        let i1 = rng.range_inclusive(i0, h);
+        let j0 = rng.range_inclusive(0, w);
+        let j1 = rng.range_inclusive(j0, w);
+        let s: usize = naive[i0..i1]
+            .iter()
+            .map(|r| r[j0..j1].iter().sum::<usize>())
+            .sum();
+        assert_eq!(ft.sum_rect(i0, i1, j0, j1).0, s);
+    }
+}
+
+#[test]
+fn dense_2d() {
+    let mut rng = Xoshiro::seed_from_u64(3);
+    let (h, w) = (7, 9);
+    let mut ft = FenwickTree2D::new(h, w);
+    let mut naive = vec![vec![0; w]; h];
+    for _ in 0..300 {
+        let i = rng.range(0, h);
+        let j = rng.range(0, w);
+        let x = rng.range(-10, 10);
+        ft.add(i, j, Sum(x));
+        naive[i][j] += x;
+        let i0 = rng.range_inclusive(0, h);
+        let i1 = rng.range_inclusive(i0, h);
+        let j0 = rng.range_inclusive(0, w);
+        let j1 = rng.range_inclusive(j0, w);
+        let s: i64 = naive[i0..i1]
+            .iter()
+            .map(|r| r[j0..j1].iter().sum::<i64>())
+            .sum();
+        assert_eq!(ft.sum_rect(i0, i1, j0, j1).0, s);
+    }
+}
+
+#[test]
+fn sparse_2d_rectangle_count() {
+    let mut rng = Xoshiro::seed_from_u64(4);
+    let points: Vec<(i64, i64)> = (0..100)
+        .map(|_| (rng.range(-1000, 1000), rng.range(-1000, 1000)))
+        .collect();
+    let ft = SparseFenwickTree2D::from_weighted(
+        points.iter().map(|&(x, y)| (x, y, Sum(1usize))).collect(),
+    );
+    for _ in 0..300 {
+        let x0 = rng.range(-1100, 1100);
+        let x1 = rng.range(x0, 1101);
+        let y0 = rng.range(-1100, 1100);
+        let y1 = rng.range(y0, 1101);
+        let naive = points
+            .iter()
+            .filter(|&&(x, y)| x0 <= x && x < x1 && y0 <= y && y < y1)
+            .count();
+        assert_eq!(ft.sum_rect(&x0, &x1, &y0, &y1).0, naive);
+    }
+}
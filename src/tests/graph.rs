@@ -0,0 +1,167 @@
+use crate::bipartite_matching::*;
+use crate::graph::*;
+use crate::graph2::{Graph as _, IndexedGraph};
+use crate::max_flow::*;
+use crate::random::*;
+use crate::scc::*;
+use crate::tsort::*;
+
+fn random_edges(rng: &mut Xoshiro, n: usize, m: usize) -> Vec<(usize, usize)> {
+    (0..m).map(|_| (rng.range(0, n), rng.range(0, n))).collect()
+}
+
+fn adj_list(n: usize, edges: &[(usize, usize)]) -> Vec<Vec<usize>> {
+    let mut g = vec![vec![]; n];
+    for &(u, v) in edges {
+        g[u].push(v);
+    }
+    g
+}
+
+#[test]
+fn build() {
+    let mut rng = Xoshiro::seed_from_u64(1);
+    for _ in 0..100 {
+        let n = rng.range_inclusive(1, 10);
+        let m = rng.range(0, 4 * n);
+        let edges = random_edges(&mut rng, n, m);
+        let adj = adj_list(n, &edges);
+        let g = Graph::from_edges(n, edges.iter().copied());
+        assert_eq!(g.len(), n);
+        assert_eq!(g.num_edges(), m);
+        assert_eq!(g.num_nodes(), n);
+        for u in 0..n {
+            assert_eq!(g[u], adj[u]);
+            assert!(g.neighbors(u).eq(adj[u].iter().copied()));
+        }
+
+        let rev = adj_list(n, &edges.iter().map(|&(u, v)| (v, u)).collect::<Vec<_>>());
+        let both: Vec<_> = edges.iter().flat_map(|&(u, v)| [(u, v), (v, u)]).collect();
+        let both = adj_list(n, &both);
+        let undirected = Graph::from_undirected_edges(n, edges.iter().copied());
+        for u in 0..n {
+            let mut r = g.reversed()[u].to_vec();
+            r.sort_unstable();
+            let mut rev_u = rev[u].clone();
+            rev_u.sort_unstable();
+            assert_eq!(r, rev_u);
+            let mut a = g.undirected()[u].to_vec();
+            a.sort_unstable();
+            let mut b = undirected[u].to_vec();
+            b.sort_unstable();
+            let mut c = both[u].clone();
+            c.sort_unstable();
+            assert_eq!(a, c);
+            assert_eq!(b, c);
+        }
+
+        let labeled =
+            LabeledGraph::from_edges(n, edges.iter().enumerate().map(|(i, &(u, v))| (u, v, i)));
+        assert_eq!(labeled.num_edges(), m);
+        for u in 0..n {
+            assert!(labeled[u].iter().all(|&(v, i)| edges[i] == (u, v)));
+            assert!(labeled.reversed()[u]
+                .iter()
+                .all(|&(v, i)| edges[i] == (v, u)));
+            assert_eq!(labeled.unlabeled()[u], adj[u]);
+        }
+    }
+}
+
+#[test]
+fn builder() {
+    let mut b = GraphBuilder::new(4);
+    b.add_edge(0, 1)
+        .add_edge(2, 3)
+        .add_undirected_edge(1, 2)
+        .add_edge(0, 3);
+    assert_eq!(b.num_nodes(), 4);
+    assert_eq!(b.num_edges(), 5);
+    let g = b.build();
+    assert_eq!(g[0], [1, 3]);
+    assert_eq!(g[1], [2]);
+    assert_eq!(g[2], [3, 1]);
+    assert!(g[3].is_empty());
+    assert_eq!(g.dist_bfs(0), [0, 1, 2, 1]);
+
+    let mut b = GraphBuilder::with_capacity(3, 4);
+    b.add_labeled_edge(0, 1, "a")
+        .add_labeled_undirected_edge(1, 2, "b");
+    let g = b.build_labeled();
+    assert_eq!(g[0], [(1, "a")]);
+    assert_eq!(g[1], [(2, "b")]);
+    assert_eq!(g[2], [(1, "b")]);
+    assert!(GraphBuilder::new(0).build().is_empty());
+}
+
+#[test]
+fn algorithms_on_csr() {
+    let mut rng = Xoshiro::seed_from_u64(2);
+    for _ in 0..100 {
+        let n = rng.range_inclusive(1, 10);
+        let m = rng.range(0, 2 * n);
+        let edges = random_edges(&mut rng, n, m);
+        let adj = adj_list(n, &edges);
+        let g = Graph::from_edges(n, edges.iter().copied());
+
+        assert_eq!(scc_graph(&g), scc(n, |u| adj[u].iter().copied()));
+        assert_eq!(scc_graph(&g), scc_graph(&adj[..]));
+        assert_eq!(condense_graph(&g), condense(n, |u| adj[u].iter().copied()));
+        let (count, comp) = scc_graph(&g);
+        let acyclic = count == n && edges.iter().all(|&(u, v)| u != v);
+        match tsort(&g) {
+            Some(ord) => {
+                assert!(acyclic);
+                let mut pos = vec![0; n];
+                for (i, &u) in ord.iter().enumerate() {
+                    pos[u] = i;
+                }
+                assert!(edges.iter().all(|&(u, v)| pos[u] < pos[v]));
+            }
+            None => assert!(!acyclic, "{:?}", comp),
+        }
+        assert_eq!(g.tsort().is_some(), acyclic);
+
+        let n_end = rng.range_inclusive(1, 10);
+        // Right nodes must be nodes of the graph too; the extra left ones
+        // have no edges.
+        let bip = Graph::from_edges(
+            n.max(n_end),
+            (0..m).map(|_| (rng.range(0, n), rng.range(0, n_end))),
+        );
+        let matching = bipartite_matching(&bip, n_end);
+        let bip_adj: Vec<Vec<usize>> = (0..bip.len()).map(|u| bip[u].to_vec()).collect();
+        assert_eq!(bipartite_matching(&bip_adj, n_end), matching);
+        let n = bip.len();
+        let mut flow = MaxFlow::new(n + n_end + 2);
+        for u in 0..n {
+            flow.edge(n + n_end, u, 1);
+            for &v in &bip[u] {
+                flow.edge(u, n + v, 1);
+            }
+        }
+        for v in 0..n_end {
+            flow.edge(n + v, n + n_end + 1, 1);
+        }
+        assert_eq!(matching.len() as u64, flow.run(n + n_end, n + n_end + 1));
+        assert!(matching.iter().all(|&(l, r)| bip[l].contains(&r)));
+    }
+}
+
+#[test]
+fn max_flow_from_graph() {
+    let g = LabeledGraph::from_edges(
+        4,
+        [
+            (0, 1, 3),
+            (0, 2, 2),
+            (1, 2, 1),
+            (1, 3, 2),
+            (2, 3, 3),
+            (0, 3, 1),
+        ],
+    );
+    let mut flow = MaxFlow::from_graph(&g);
+    assert_eq!(flow.run(0, 3), 6);
+    assert_eq!(flow.min_cut(0), [true, false, false, false]);
+}
@@ -0,0 +1,225 @@
+use crate::hld::*;
+use crate::lazy_seg_tree::{self, LazySegTree};
+use crate::modint2::*;
+use crate::random::*;
+use crate::segtree::*;
+
+pub(super) fn random_parents<R: Rng>(rng: &mut R, n: usize) -> Vec<usize> {
+    let perm = {
+        let mut p: Vec<usize> = (0..n).collect();
+        rng.shuffle(&mut p);
+        p
+    };
+    let mut par = vec![0; n];
+    par[perm[0]] = perm[0];
+    for i in 1..n {
+        par[perm[i]] = perm[rng.range(0, i)];
+    }
+    par
+}
+
+/// Vertices on the path from `u` to `v`, where the root has `par[root]` equal
+/// to itself or `!0`.
+pub(super) fn naive_path(par: &[usize], mut u: usize, mut v: usize) -> Vec<usize> {
+    let depth = |mut v: usize| {
+        let mut d = 0;
+        while par[v] != v && par[v] != !0 {
+            v = par[v];
+            d += 1;
+        }
+        d
+    };
+    let mut up = vec![];
+    let mut down = vec![];
+    let (mut du, mut dv) = (depth(u), depth(v));
+    while du > dv {
+        up.push(u);
+        u = par[u];
+        du -= 1;
+    }
+    while dv > du {
+        down.push(v);
+        v = par[v];
+        dv -= 1;
+    }
+    while u != v {
+        up.push(u);
+        down.push(v);
+        u = par[u];
+        v = par[v];
+    }
+    up.push(u);
+    up.extend(down.into_iter().rev());
+    up
+}
+
+#[test]
+fn lca_dist_kth() {
+    let mut rng = Xoshiro::seed_from_u64(1);
+    for n in 1..40 {
+        let par = random_parents(&mut rng, n);
+        let hld = Hld::from_parents(&par);
+        let mut adj = vec![vec![]; n];
+        for (v, &p) in par.iter().enumerate() {
+            if p != v {
+                adj[p].push(v);
+                adj[v].push(p);
+            }
+        }
+        let root = hld.root();
+        let hld_adj = Hld::new(&adj[..], root);
+        for _ in 0..100 {
+            let u = rng.range(0, n);
+            let v = rng.range(0, n);
+            let path = naive_path(&par, u, v);
+            let lca = *path.iter().min_by_key(|&&w| hld.depth(w)).unwrap();
+            assert_eq!(hld.lca(u, v), lca);
+            assert_eq!(hld_adj.lca(u, v), lca);
+            assert_eq!(hld.dist(u, v), path.len() - 1);
+            let up = naive_path(&par, u, root);
+            for k in 0..=up.len() {
+                assert_eq!(hld.kth_ancestor(u, k), up.get(k).copied());
+            }
+        }
+        for v in 0..n {
+            let sub = hld.subtree(v);
+            for w in 0..n {
+                let inside = naive_path(&par, w, root).contains(&v);
+                assert_eq!(sub.contains(&hld.pos(w)), inside);
+            }
+        }
+    }
+}
+
+const M: u32 = 998244353;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct Affine(Mint<M>, Mint<M>);
+
+impl Affine {
+    // (g . f)(x) = g(f(x)), applying `self` first
+    fn then(&self, g: &Self) -> Self {
+        Affine(g.0 * self.0, g.0 * self.1 + g.1)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct BothWays(Affine, Affine);
+
+impl Monoid for BothWays {
+    fn id() -> Self {
+        let id = Affine(mint(1), mint(0));
+        BothWays(id, id)
+    }
+    fn op(&self, other: &Self) -> Self {
+        BothWays(self.0.then(&other.0), other.1.then(&self.1))
+    }
+}
+
+#[test]
+fn path_non_commutative() {
+    let mut rng = Xoshiro::seed_from_u64(2);
+    for n in 1..30 {
+        let par = random_parents(&mut rng, n);
+        let hld = Hld::from_parents(&par);
+        let mut a: Vec<Affine> = (0..n)
+            .map(|_| Affine(mint(rng.range(0, M)), mint(rng.range(0, M))))
+            .collect();
+        let mut st: SegTree<BothWays> = hld.order().iter().map(|&v| BothWays(a[v], a[v])).collect();
+        for _ in 0..100 {
+            if rng.range(0, 4) == 0 {
+                let v = rng.range(0, n);
+                a[v] = Affine(mint(rng.range(0, M)), mint(rng.range(0, M)));
+                st.set(hld.pos(v), BothWays(a[v], a[v]));
+            }
+            let u = rng.range(0, n);
+            let v = rng.range(0, n);
+            let edge = rng.range(0, 2) == 1;
+            let path = naive_path(&par, u, v);
+            let lca = hld.lca(u, v);
+            let naive = path
+                .iter()
+                .filter(|&&w| !edge || w != lca)
+                .fold(BothWays::id().0, |acc, &w| acc.then(&a[w]));
+            let res = hld.fold_path(u, v, edge, |r, rev| {
+                let s = st.sum(r.start, r.end);
+                if rev {
+                    BothWays(s.1, s.0)
+                } else {
+                    s
+                }
+            });
+            assert_eq!(res.0, naive);
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct SumLen(i64, i64);
+
+impl lazy_seg_tree::Monoid for SumLen {
+    fn id() -> Self {
+        SumLen(0, 0)
+    }
+    fn op(&self, other: &Self) -> Self {
+        SumLen(self.0 + other.0, self.1 + other.1)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct Add(i64);
+
+impl lazy_seg_tree::Monoid for Add {
+    fn id() -> Self {
+        Add(0)
+    }
+    fn op(&self, other: &Self) -> Self {
+        Add(self.0 + other.0)
+    }
+}
+
+impl lazy_seg_tree::Action<SumLen> for Add {
+    fn act(&self, x: &SumLen) -> SumLen {
+        SumLen(x.0 + self.0 * x.1, x.1)
+    }
+}
+
+#[test]
+fn path_lazy() {
+    let mut rng = Xoshiro::seed_from_u64(3);
+    for n in 1..30 {
+        let par = random_parents(&mut rng, n);
+        let hld = Hld::from_parents(&par);
+        let mut a = vec![0i64; n];
+        let mut st: LazySegTree<SumLen, Add> = (0..n).map(|_| SumLen(0, 1)).collect();
+        for _ in 0..100 {
+            let u = rng.range(0, n);
+            let v = rng.range(0, n);
+            let path = naive_path(&par, u, v);
+            if rng.range(0, 2) == 0 {
+                let x = rng.range(-100, 100);
+                for (r, _) in hld.path(u, v, false) {
+                    st.apply(r.start, r.end, &Add(x));
+                }
+                for &w in &path {
+                    a[w] += x;
+                }
+            } else {
+                let sum: i64 = hld
+                    .path(u, v, false)
+                    .into_iter()
+                    .map(|(r, _)| st.prod(r.start, r.end).0)
+                    .sum();
+                assert_eq!(sum, path.iter().map(|&w| a[w]).sum::<i64>());
+                let w = rng.range(0, n);
+                assert_eq!(st.prod(hld.subtree(w).start, hld.subtree(w).end).0, {
+                    let sub = hld.subtree(w);
+                    (0..n)
+                        .filter(|&x| sub.contains(&hld.pos(x)))
+                        .map(|x| a[x])
+                        .sum::<i64>()
+                });
+            }
+        }
+    }
+}
@@ -15,6 +15,19 @@ fn gcd() {
     assert_eq!(0.gcd(0), 0);
 }
 
+#[test]
+fn ext_gcd_bezout() {
+    assert_eq!(<i64 as UInt>::ONE, 1);
+    assert_eq!(<u32 as UInt>::ONE, 1);
+    for x in -30i64..30 {
+        for y in -30i64..30 {
+            let (g, a, b) = ext_gcd(x, y);
+            assert_eq!(g, x.gcd(y));
+            assert_eq!(a * x + b * y, g, "{} {}", x, y);
+        }
+    }
+}
+
 #[test]
 fn div_floor() {
     #![allow(unstable_name_collisions)]
@@ -64,3 +77,68 @@ fn isqrt() {
         assert_eq!((i * i - 1).isqrt(), i - 1);
     }
 }
+
+#[test]
+fn crt_many() {
+    use crate::random::*;
+    let mut rng = Xoshiro::seed_from_u64(1);
+    for _ in 0..1000 {
+        let n = rng.range(0, 5);
+        let ms: Vec<i64> = (0..n).map(|_| rng.range_inclusive(1, 12)).collect();
+        let rs: Vec<i64> = ms
+            .iter()
+            .map(|&m| rng.range(-20, 20) * m + rng.range(0, m))
+            .collect();
+        let rs: Vec<i64> = if rng.range(0, 2) == 0 {
+            // Make the system consistent.
+            let x = rng.range(0, 100000);
+            ms.iter().map(|&m| x % m + rng.range(-3, 3) * m).collect()
+        } else {
+            rs
+        };
+        let l = ms.iter().fold(1, |l, &m| l.lcm(m));
+        let naive = (0..l).find(|x| rs.iter().zip(&ms).all(|(r, m)| (x - r) % m == 0));
+        let res = crate::int::crt_many(rs.iter().copied().zip(ms.iter().copied()));
+        assert_eq!(res, naive.map(|x| (x, l)));
+    }
+    let p = 1_000_000_007i64;
+    let q = 998_244_353i64;
+    let x = 123_456_789_012_345_678i64;
+    assert_eq!(
+        crate::int::crt_many([(x % p, p), (x % q, q), (x % 6, 6)]),
+        Some((x, p * q * 6))
+    );
+}
+
+#[test]
+fn garner() {
+    let mods = [
+        754974721u64,
+        167772161,
+        469762049,
+        1_000_000_007,
+        18446744073709551557,
+    ];
+    let x: u128 = 123_456_789_012_345_678_901_234_567_890;
+    let rems: Vec<u64> = mods.iter().map(|&m| (x % m as u128) as u64).collect();
+    for m in [1u64, 2, 998244353, 1 << 63, u64::MAX] {
+        assert_eq!(crate::int::garner(&rems, &mods, m), (x % m as u128) as u64);
+    }
+    assert_eq!(crate::int::garner(&[], &[], 10), 0);
+    assert_eq!(crate::int::garner(&[3, 4], &[5, 1], 100), 3);
+
+    let g = Garner::new(mods);
+    let rems: [u64; 5] = rems.try_into().unwrap();
+    let t = g.digits(rems);
+    let mut y = 0u128;
+    let mut c = 1u128;
+    for (&ti, &mi) in t.iter().zip(&mods) {
+        assert!(ti < mi);
+        y = y.wrapping_add(c.wrapping_mul(ti as u128));
+        c = c.wrapping_mul(mi as u128);
+    }
+    // The first four moduli already exceed x.
+    assert_eq!(t[4], 0);
+    assert_eq!(y, x);
+    assert_eq!(g.solve(rems, 998244353), (x % 998244353) as u64);
+}
@@ -0,0 +1,55 @@
+use crate::algebra::*;
+use crate::lazy_seg_tree::*;
+use crate::random::*;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct Add(i64);
+
+impl Monoid for Add {
+    fn id() -> Self {
+        Add(0)
+    }
+    fn op(&self, other: &Self) -> Self {
+        Add(self.0 + other.0)
+    }
+}
+
+impl Action<(Sum<i64>, Sum<i64>)> for Add {
+    fn act(&self, x: &(Sum<i64>, Sum<i64>)) -> (Sum<i64>, Sum<i64>) {
+        (Sum(x.0 .0 + self.0 * x.1 .0), x.1)
+    }
+}
+
+#[test]
+fn max_right_min_left() {
+    let mut rng = Xoshiro::seed_from_u64(1);
+    for n in 0..40 {
+        let mut a: Vec<i64> = (0..n).map(|_| rng.range(0, 10)).collect();
+        let mut st: LazySegTree<(Sum<i64>, Sum<i64>), Add> =
+            a.iter().map(|&x| (Sum(x), Sum(1))).collect();
+        assert_eq!(st.len(), n);
+        for _ in 0..200 {
+            if rng.range(0, 3) == 0 {
+                let l = rng.range_inclusive(0, n);
+                let r = rng.range_inclusive(0, n);
+                let (l, r) = (l.min(r), l.max(r));
+                let x = rng.range(0, 5);
+                st.apply(l, r, &Add(x));
+                for y in &mut a[l..r] {
+                    *y += x;
+                }
+            }
+            let i = rng.range_inclusive(0, n);
+            let x = rng.range(0, 80);
+            let naive_r = (i..=n)
+                .rev()
+                .find(|&r| a[i..r].iter().sum::<i64>() <= x)
+                .unwrap();
+            assert_eq!(st.max_right(i, |s| s.0 .0 <= x), naive_r);
+            let naive_l = (0..=i).find(|&l| a[l..i].iter().sum::<i64>() <= x).unwrap();
+            assert_eq!(st.min_left(i, |s| s.0 .0 <= x), naive_l);
+            let l = rng.range_inclusive(0, i);
+            assert_eq!(st.prod(l, i).0 .0, a[l..i].iter().sum::<i64>());
+        }
+    }
+}
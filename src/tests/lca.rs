@@ -0,0 +1,81 @@
+use super::hld::{naive_path, random_parents};
+use crate::algebra::Sum;
+use crate::lca::*;
+use crate::random::*;
+
+#[test]
+fn lca_backends() {
+    let mut rng = Xoshiro::seed_from_u64(1);
+    for n in 1..40 {
+        let mut par = random_parents(&mut rng, n);
+        let root = (0..n).find(|&v| par[v] == v).unwrap();
+        par[root] = !0;
+        let mut adj = vec![vec![]; n];
+        for (v, &p) in par.iter().enumerate() {
+            if p != !0 {
+                adj[p].push(v);
+                adj[v].push(p);
+            }
+        }
+        let euler = Lca::from_parents(&par);
+        let doubling = DoublingLca::from_parents(&par);
+        let from_graph = Lca::new(&adj[..], root);
+        let doubling_graph = DoublingLca::new(&adj[..], root);
+        let mut queries = vec![];
+        for u in 0..n {
+            assert_eq!(euler.parent(u), Some(par[u]).filter(|&p| p != !0));
+            assert_eq!(doubling.parent(u), euler.parent(u));
+            for v in 0..n {
+                let path = naive_path(&par, u, v);
+                let w = *path
+                    .iter()
+                    .min_by_key(|&&x| naive_path(&par, x, root).len())
+                    .unwrap();
+                queries.push((u, v));
+                assert_eq!(euler.lca(u, v), w);
+                assert_eq!(doubling.lca(u, v), w);
+                assert_eq!(from_graph.lca(u, v), w);
+                assert_eq!(doubling_graph.lca(u, v), w);
+                assert_eq!(euler.dist(u, v), path.len() - 1);
+                assert_eq!(doubling.dist(u, v), path.len() - 1);
+                for k in 0..=path.len() {
+                    assert_eq!(euler.jump(u, v, k), path.get(k).copied());
+                    assert_eq!(doubling.jump(u, v, k), path.get(k).copied());
+                }
+            }
+            let up = naive_path(&par, u, root);
+            for k in 0..=up.len() {
+                assert_eq!(euler.kth_ancestor(u, k), up.get(k).copied());
+                assert_eq!(doubling.kth_ancestor(u, k), up.get(k).copied());
+            }
+        }
+        let res = offline_lca(&par, &queries);
+        for (&(u, v), &w) in queries.iter().zip(&res) {
+            assert_eq!(w, euler.lca(u, v));
+        }
+    }
+}
+
+#[test]
+fn doubling_fold() {
+    let mut rng = Xoshiro::seed_from_u64(2);
+    for n in 1..30 {
+        let par = random_parents(&mut rng, n);
+        let w: Vec<i64> = (0..n).map(|_| rng.range(-100, 100)).collect();
+        let lca = DoublingLca::with_weights(&par, |v| Sum(w[v]));
+        for u in 0..n {
+            for v in 0..n {
+                let path = naive_path(&par, u, v);
+                let top = lca.lca(u, v);
+                let expected: i64 = path.iter().filter(|&&x| x != top).map(|&x| w[x]).sum();
+                assert_eq!(lca.fold_path(u, v).0, expected);
+            }
+            for k in 0..=lca.depth(u) {
+                let up = naive_path(&par, u, lca.kth_ancestor(u, k).unwrap());
+                let (a, s) = lca.ascend(u, k);
+                assert_eq!(a, *up.last().unwrap());
+                assert_eq!(s.0, up[..k].iter().map(|&x| w[x]).sum::<i64>());
+            }
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use crate::linear_recurrence::*;
+use crate::modint2::*;
+use crate::modint_poly::Poly;
+use crate::random::*;
+
+type M = ConstMod<998244353>;
+
+#[test]
+fn fibonacci() {
+    let s: Vec<ModInt<M>> = [0, 1, 1, 2, 3, 5, 8, 13].map(ModInt::from).to_vec();
+    let q = berlekamp_massey(&s);
+    assert_eq!(q.len(), 3);
+    assert_eq!(q, Poly::from(vec![1, -1, -1]));
+    let p = Poly::from(vec![0, 1]);
+    let mut a = ModInt::<M>::ZERO;
+    let mut b = ModInt::ONE;
+    for k in 0..100 {
+        assert_eq!(kth_term(&p, &q, k), a);
+        assert_eq!(nth_term(&s, k), a);
+        (a, b) = (b, a + b);
+    }
+    // F(10^18) mod 998244353
+    let f = nth_term(&s, 1_000_000_000_000_000_000);
+    let mut m = [[ModInt::<M>::ZERO; 2]; 2];
+    let mut r = [[ModInt::ONE, ModInt::ZERO], [ModInt::ZERO, ModInt::ONE]];
+    m[0][1] = ModInt::ONE;
+    m[1][0] = ModInt::ONE;
+    m[1][1] = ModInt::ONE;
+    let mul = |x: [[ModInt<M>; 2]; 2], y: [[ModInt<M>; 2]; 2]| {
+        let mut z = [[ModInt::ZERO; 2]; 2];
+        for i in 0..2 {
+            for j in 0..2 {
+                for k in 0..2 {
+                    z[i][j] += x[i][k] * y[k][j];
+                }
+            }
+        }
+        z
+    };
+    let mut e = 1_000_000_000_000_000_000u64;
+    while e > 0 {
+        if e & 1 == 1 {
+            r = mul(r, m);
+        }
+        m = mul(m, m);
+        e >>= 1;
+    }
+    assert_eq!(f, r[0][1]);
+}
+
+#[test]
+fn random_recurrence() {
+    let mut rng = Xoshiro::seed_from_u64(1);
+    for l in 0..20 {
+        let c: Vec<ModInt<M>> = (0..l)
+            .map(|_| ModInt::from(rng.range(0u32, 998244353)))
+            .collect();
+        let mut s: Vec<ModInt<M>> = (0..l)
+            .map(|_| ModInt::from(rng.range(0u32, 998244353)))
+            .collect();
+        for i in l..200 {
+            let x = (0..l).map(|j| c[j] * s[i - 1 - j]).sum();
+            s.push(x);
+        }
+        let q = berlekamp_massey(&s[..2 * l]);
+        assert!(q.len() <= l + 1);
+        for i in q.len() - 1..200 {
+            let x: ModInt<M> = (0..q.len()).map(|j| q[j] * s[i - j]).sum();
+            assert_eq!(x.get(), 0);
+        }
+        for k in [0, 1, 50, 199] {
+            assert_eq!(nth_term(&s[..2 * l], k), s[k as usize]);
+        }
+    }
+    assert_eq!(berlekamp_massey::<M>(&[]).len(), 1);
+    let zeros = vec![ModInt::<M>::ZERO; 5];
+    assert_eq!(nth_term(&zeros, 100), ModInt::ZERO);
+}
@@ -0,0 +1,137 @@
+use crate::dsu::Dsu;
+use crate::lowlink::*;
+use crate::random::*;
+
+fn random_graph(rng: &mut Xoshiro, n: usize, m: usize) -> (Vec<(usize, usize)>, Vec<Vec<usize>>) {
+    let mut edges = vec![];
+    let mut g = vec![vec![]; n];
+    for _ in 0..m {
+        let u = rng.range(0, n);
+        let v = rng.range(0, n);
+        edges.push((u, v));
+        g[u].push(v);
+        if u != v {
+            g[v].push(u);
+        }
+    }
+    (edges, g)
+}
+
+fn num_components(
+    n: usize,
+    edges: impl IntoIterator<Item = (usize, usize)>,
+    removed: Option<usize>,
+) -> usize {
+    let mut dsu = Dsu::new(n);
+    for (u, v) in edges {
+        if Some(u) != removed && Some(v) != removed {
+            dsu.unite(u, v);
+        }
+    }
+    (0..n)
+        .filter(|&u| Some(u) != removed && dsu.is_root(u))
+        .count()
+}
+
+#[test]
+fn bridges_and_articulation_points() {
+    let mut rng = Xoshiro::seed_from_u64(1);
+    for _ in 0..300 {
+        let n = rng.range_inclusive(1, 10);
+        let m = rng.range(0, 2 * n);
+        let (edges, g) = random_graph(&mut rng, n, m);
+        let ll = Lowlink::new(n, |u| g[u].iter().copied());
+        let from_graph = Lowlink::from_graph(&g[..]);
+        assert_eq!(from_graph.bridges(), ll.bridges());
+        assert_eq!(from_graph.articulation_points(), ll.articulation_points());
+        let c = num_components(n, edges.iter().copied(), None);
+        let mut naive_bridges: Vec<(usize, usize)> = (0..m)
+            .filter(|&i| {
+                let rest = edges.iter().enumerate().filter(|&(j, _)| j != i);
+                num_components(n, rest.map(|(_, &e)| e), None) > c
+            })
+            .map(|i| (edges[i].0.min(edges[i].1), edges[i].0.max(edges[i].1)))
+            .collect();
+        naive_bridges.sort();
+        let mut bridges: Vec<_> = ll
+            .bridges()
+            .iter()
+            .map(|&(u, v)| (u.min(v), v.max(u)))
+            .collect();
+        bridges.sort();
+        assert_eq!(bridges, naive_bridges);
+        let naive_cut: Vec<usize> = (0..n)
+            .filter(|&u| {
+                let isolated = g[u].iter().all(|&v| v == u);
+                num_components(n, edges.iter().copied(), Some(u)) > c - isolated as usize
+            })
+            .collect();
+        assert_eq!(ll.articulation_points(), naive_cut);
+        // Two vertices are 2-edge-connected iff they are connected without
+        // the bridges.
+        let (count, id) = ll.two_edge_connected_components();
+        let mut dsu = Dsu::new(n);
+        for &(u, v) in &edges {
+            if !naive_bridges.contains(&(u.min(v), u.max(v))) {
+                dsu.unite(u, v);
+            }
+        }
+        assert_eq!(count, (0..n).filter(|&u| dsu.is_root(u)).count());
+        for u in 0..n {
+            for v in 0..n {
+                assert_eq!(id[u] == id[v], dsu.is_same(u, v));
+            }
+        }
+    }
+}
+
+#[test]
+fn biconnected_components() {
+    let mut rng = Xoshiro::seed_from_u64(2);
+    for _ in 0..300 {
+        let n = rng.range_inclusive(1, 10);
+        let m = rng.range(0, 2 * n);
+        let (edges, g) = random_graph(&mut rng, n, m);
+        let ll = Lowlink::new(n, |u| g[u].iter().copied());
+        let blocks = ll.biconnected_components();
+        // Every edge lies in exactly one block.
+        for &(u, v) in edges.iter().filter(|&&(u, v)| u != v) {
+            let k = blocks
+                .iter()
+                .filter(|b| b.contains(&u) && b.contains(&v))
+                .count();
+            assert_eq!(k, 1);
+        }
+        for b in &blocks {
+            let inner: Vec<(usize, usize)> = edges
+                .iter()
+                .copied()
+                .filter(|&(u, v)| u != v && b.contains(&u) && b.contains(&v))
+                .collect();
+            match b.len() {
+                1 => assert!(g[b[0]].iter().all(|&v| v == b[0])),
+                2 => assert!(!inner.is_empty()),
+                _ => {
+                    // Still connected after removing any one vertex.
+                    for &x in b {
+                        let rest = inner.iter().copied().filter(|&(u, v)| u != x && v != x);
+                        let mut dsu = Dsu::new(n);
+                        for (u, v) in rest {
+                            dsu.unite(u, v);
+                        }
+                        let roots = b.iter().filter(|&&u| u != x).map(|&u| dsu.root(u));
+                        let mut roots: Vec<usize> = roots.collect();
+                        roots.sort();
+                        roots.dedup();
+                        assert_eq!(roots.len(), 1);
+                    }
+                }
+            }
+        }
+        // The block-cut graph is a forest with one tree per component.
+        let tree = ll.block_cut_tree();
+        let num_edges = tree.iter().map(|a| a.len()).sum::<usize>() / 2;
+        let c = num_components(n, edges.iter().copied(), None);
+        assert_eq!(num_edges + c, tree.len());
+    }
+}
@@ -0,0 +1,202 @@
+use crate::hld::Hld;
+use crate::mo::*;
+use crate::random::*;
+
+struct Distinct<'a> {
+    a: &'a [usize],
+    cnt: Vec<usize>,
+    distinct: usize,
+    ans: Vec<usize>,
+}
+
+impl Distinct<'_> {
+    fn add(&mut self, x: usize) {
+        self.cnt[x] += 1;
+        if self.cnt[x] == 1 {
+            self.distinct += 1;
+        }
+    }
+    fn remove(&mut self, x: usize) {
+        self.cnt[x] -= 1;
+        if self.cnt[x] == 0 {
+            self.distinct -= 1;
+        }
+    }
+}
+
+impl Mo for Distinct<'_> {
+    fn add_left(&mut self, i: usize) {
+        self.add(self.a[i]);
+    }
+    fn add_right(&mut self, i: usize) {
+        self.add(self.a[i]);
+    }
+    fn remove_left(&mut self, i: usize) {
+        self.remove(self.a[i]);
+    }
+    fn remove_right(&mut self, i: usize) {
+        self.remove(self.a[i]);
+    }
+    fn answer(&mut self, q: usize) {
+        self.ans[q] = self.distinct;
+    }
+}
+
+impl MoTree for Distinct<'_> {
+    fn add(&mut self, v: usize) {
+        self.add(self.a[v]);
+    }
+    fn remove(&mut self, v: usize) {
+        self.remove(self.a[v]);
+    }
+    fn answer(&mut self, q: usize) {
+        self.ans[q] = self.distinct;
+    }
+}
+
+fn random_queries<R: Rng>(rng: &mut R, n: usize, q: usize) -> Vec<(usize, usize)> {
+    (0..q)
+        .map(|_| {
+            let l = rng.range_inclusive(0, n);
+            let r = rng.range_inclusive(0, n);
+            (l.min(r), l.max(r))
+        })
+        .collect()
+}
+
+#[test]
+fn distinct() {
+    let mut rng = Xoshiro::seed_from_u64(1);
+    for n in 0..50 {
+        let a: Vec<usize> = (0..n).map(|_| rng.range(0, 10)).collect();
+        let qs = random_queries(&mut rng, n, 100);
+        let naive: Vec<usize> = qs
+            .iter()
+            .map(|&(l, r)| {
+                let mut b = a[l..r].to_vec();
+                b.sort();
+                b.dedup();
+                b.len()
+            })
+            .collect();
+        for hilbert in [false, true] {
+            let mut state = Distinct {
+                a: &a,
+                cnt: vec![0; 10],
+                distinct: 0,
+                ans: vec![!0; qs.len()],
+            };
+            if hilbert {
+                mo_hilbert(n, &qs, &mut state);
+            } else {
+                mo(n, &qs, &mut state);
+            }
+            assert_eq!(state.ans, naive);
+        }
+    }
+}
+
+struct MaxFreq<'a> {
+    a: &'a [usize],
+    cnt: Vec<usize>,
+    max: usize,
+    history: Vec<usize>,
+    saved: (usize, usize),
+    ans: Vec<usize>,
+}
+
+impl MaxFreq<'_> {
+    fn add(&mut self, i: usize) {
+        let x = self.a[i];
+        self.history.push(x);
+        self.cnt[x] += 1;
+        self.max = self.max.max(self.cnt[x]);
+    }
+}
+
+impl MoRollback for MaxFreq<'_> {
+    fn add_left(&mut self, i: usize) {
+        self.add(i);
+    }
+    fn add_right(&mut self, i: usize) {
+        self.add(i);
+    }
+    fn snapshot(&mut self) {
+        self.saved = (self.history.len(), self.max);
+    }
+    fn rollback(&mut self) {
+        for x in self.history.drain(self.saved.0..) {
+            self.cnt[x] -= 1;
+        }
+        self.max = self.saved.1;
+    }
+    fn reset(&mut self) {
+        for x in self.history.drain(..) {
+            self.cnt[x] -= 1;
+        }
+        self.max = 0;
+    }
+    fn answer(&mut self, q: usize) {
+        self.ans[q] = self.max;
+    }
+}
+
+#[test]
+fn rollback_max_freq() {
+    let mut rng = Xoshiro::seed_from_u64(2);
+    for n in 0..60 {
+        let a: Vec<usize> = (0..n).map(|_| rng.range(0, 5)).collect();
+        let qs = random_queries(&mut rng, n, 100);
+        let mut state = MaxFreq {
+            a: &a,
+            cnt: vec![0; 5],
+            max: 0,
+            history: vec![],
+            saved: (0, 0),
+            ans: vec![!0; qs.len()],
+        };
+        mo_rollback(n, &qs, &mut state);
+        for (q, &(l, r)) in qs.iter().enumerate() {
+            let naive = (0..5)
+                .map(|x| a[l..r].iter().filter(|&&y| y == x).count())
+                .max()
+                .unwrap();
+            assert_eq!(state.ans[q], naive);
+        }
+    }
+}
+
+#[test]
+fn tree_distinct() {
+    let mut rng = Xoshiro::seed_from_u64(3);
+    for n in 1..40 {
+        let par: Vec<usize> = (0..n)
+            .map(|i| if i == 0 { 0 } else { rng.range(0, i) })
+            .collect();
+        let hld = Hld::from_parents(&par);
+        let a: Vec<usize> = (0..n).map(|_| rng.range(0, 6)).collect();
+        let qs: Vec<(usize, usize)> = (0..100)
+            .map(|_| (rng.range(0, n), rng.range(0, n)))
+            .collect();
+        let mut state = Distinct {
+            a: &a,
+            cnt: vec![0; 6],
+            distinct: 0,
+            ans: vec![!0; qs.len()],
+        };
+        mo_tree(&hld, &qs, &mut state);
+        for (q, &(u, v)) in qs.iter().enumerate() {
+            let w = hld.lca(u, v);
+            let mut colors = vec![a[w]];
+            for mut x in [u, v] {
+                while x != w {
+                    colors.push(a[x]);
+                    x = par[x];
+                }
+            }
+            colors.sort();
+            colors.dedup();
+            assert_eq!(state.ans[q], colors.len());
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use crate::factorize::is_prime_u64;
+use crate::mod_solve::*;
+use crate::modint2::*;
+use crate::random::*;
+
+fn naive_log(a: u64, b: u64, m: u64) -> Option<u64> {
+    let mut x = 1 % m;
+    for i in 0..=2 * m {
+        if x == b % m {
+            return Some(i);
+        }
+        x = x * a % m;
+    }
+    None
+}
+
+#[test]
+fn discrete_log_small() {
+    for m in 1..60 {
+        for a in 0..m {
+            for b in 0..m {
+                assert_eq!(
+                    discrete_log(a, b, m),
+                    naive_log(a, b, m),
+                    "{} {} {}",
+                    a,
+                    b,
+                    m
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn discrete_log_large() {
+    let mut rng = Xoshiro::seed_from_u64(1);
+    for _ in 0..100 {
+        let m = rng.range_inclusive(1, 1_000_000_000u64);
+        let a = rng.range(0, m);
+        let x = rng.range(0, 100_000u64);
+        let b = pow_mod(a, x, m);
+        let y = discrete_log(a, b, m).unwrap();
+        assert!(y <= x);
+        assert_eq!(pow_mod(a, y, m), b);
+    }
+    let p = 998244353;
+    assert_eq!(
+        discrete_log(3, pow_mod(3, 123456789, p), p),
+        Some(123456789)
+    );
+    let a = Mint::<998244353>::new(3);
+    assert_eq!(a.discrete_log(Pow::pow(a, 123456789u32)), Some(123456789));
+    assert_eq!(Mint::<998244353>::new(0).discrete_log(Mint::new(5)), None);
+}
+
+#[test]
+fn sqrt_mod_all() {
+    for p in (2..300).filter(|&p| is_prime_u64(p)) {
+        for a in 0..p {
+            let naive = (0..p).find(|&x| x * x % p == a);
+            assert_eq!(sqrt_mod(a, p), naive, "{} {}", a, p);
+        }
+    }
+    let mut rng = Xoshiro::seed_from_u64(2);
+    for p in [998244353, 1_000_000_007, (1 << 61) - 1, 4611686018427387847] {
+        for _ in 0..100 {
+            let x = rng.range(0, p);
+            let a = (x as u128 * x as u128 % p as u128) as u64;
+            assert_eq!(sqrt_mod(a, p), Some(x.min(p - x)));
+        }
+    }
+}
+
+#[test]
+fn kth_root_mod_small() {
+    for p in (2..100).filter(|&p| is_prime_u64(p)) {
+        for k in 0..2 * p {
+            for a in 0..p {
+                let exists = (0..p).any(|x| pow_mod(x, k, p) == a);
+                match kth_root_mod(a, k, p) {
+                    Some(x) => assert_eq!(pow_mod(x, k, p), a, "{} {} {}", a, k, p),
+                    None => assert!(!exists, "{} {} {}", a, k, p),
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn kth_root_mod_large() {
+    let mut rng = Xoshiro::seed_from_u64(3);
+    for p in [998244353u64, 1_000_000_007, 754974721, 1_000_000_009] {
+        for _ in 0..50 {
+            let x = rng.range(1, p);
+            let k = match rng.range(0, 3) {
+                0 => rng.range(1, 100),
+                1 => (p - 1) / [2, 4, 8, 3, 5][rng.range(0, 5)],
+                _ => rng.range(1, 1 << 40),
+            };
+            let a = pow_mod(x, k, p);
+            let y = kth_root_mod(a, k, p).unwrap();
+            assert_eq!(pow_mod(y, k, p), a);
+        }
+    }
+    let a = Pow::pow(Mint::<998244353>::new(5), 1u32 << 20);
+    let y = a.kth_root(1 << 20).unwrap();
+    assert_eq!(Pow::pow(y, 1u32 << 20), a);
+    assert_eq!(Mint::<998244353>::new(3).kth_root(2), None);
+}
+
+fn pow_mod(mut a: u64, mut e: u64, m: u64) -> u64 {
+    let mut res = 1 % m;
+    while e > 0 {
+        if e & 1 == 1 {
+            res = (res as u128 * a as u128 % m as u128) as u64;
+        }
+        a = (a as u128 * a as u128 % m as u128) as u64;
+        e >>= 1;
+    }
+    res
+}
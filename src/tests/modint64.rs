@@ -0,0 +1,41 @@
+use crate::modint2::Pow;
+use crate::modint64::*;
+use crate::random::*;
+
+#[test]
+fn ops_random() {
+    let mut rng = Xoshiro::seed_from_u64(1);
+    for m in [2, 6, 1 << 40, (1 << 61) - 1, 1 << 62] {
+        set_var_mod64(m);
+        for _ in 0..1000 {
+            let x = rng.range(0, m);
+            let y = rng.range(0, m);
+            let a = ModInt64::<VarMod64>::new(x);
+            let b = ModInt64::<VarMod64>::new(y);
+            assert_eq!((a + b).get(), ((x as u128 + y as u128) % m as u128) as u64);
+            assert_eq!(
+                (a - b).get(),
+                ((x as u128 + m as u128 - y as u128) % m as u128) as u64
+            );
+            assert_eq!((a * b).get(), (x as u128 * y as u128 % m as u128) as u64);
+            assert_eq!((-a + a).get(), 0);
+        }
+        assert_eq!(ModInt64::<VarMod64>::from(-1i64).get(), m - 1);
+    }
+}
+
+#[test]
+fn inv_pow() {
+    type M = Mint64<{ (1 << 61) - 1 }>;
+    let a = M::new(987654321987654321);
+    assert_eq!(a * a.inv(), M::ONE);
+    assert_eq!(a.pow((1u64 << 61) - 3), a.inv());
+    let v: Vec<M> = (1..=20u32).map(M::from).collect();
+    assert_eq!(
+        v.iter().product::<M>().get(),
+        2432902008176640000 % ((1 << 61) - 1)
+    );
+    assert_eq!(v.iter().sum::<M>().get(), 210);
+    type E = Mint64<{ 1 << 62 }>;
+    assert_eq!((E::new(3) / E::new(5) * E::new(5)).get(), 3);
+}
@@ -50,3 +50,144 @@ fn div_rem_random() {
         }
     }
 }
+
+fn random_poly(rng: &mut Xoshiro, n: usize) -> Poly<ConstMod<998244353>> {
+    (0..n)
+        .map(|_| ModInt::from(rng.range(0u32, 998244353)))
+        .collect()
+}
+
+fn prefix<M: Modulo>(f: &Poly<M>, n: usize) -> Poly<M> {
+    let mut f = f.clone();
+    if n == 0 {
+        return Poly::zero();
+    }
+    f.truncate(n - 1);
+    f
+}
+
+#[test]
+fn derivative_integral() {
+    type M = ConstMod<998244353>;
+    let f = Poly::<M>::from(vec![5, 1, 2, 3]);
+    assert_eq!(f.derivative(), Poly::from(vec![1, 4, 9]));
+    assert_eq!(f.derivative().integral(), Poly::from(vec![0, 1, 2, 3]));
+    assert_eq!(Poly::<M>::zero().derivative(), Poly::zero());
+}
+
+#[test]
+fn log_exp_random() {
+    let mut rng = Xoshiro::seed_from_u64(2);
+    for n in [0, 1, 2, 3, 10, 33, 100] {
+        let mut f = random_poly(&mut rng, n);
+        *f.coef_mut(0) = ModInt::ZERO;
+        f.normalize();
+        let g = f.exp(n);
+        assert!(g.len() <= n);
+        if n > 0 {
+            assert_eq!(g.coef(0), ModInt::ONE);
+            assert_eq!(g.log(n), f);
+            // exp(f)' = f' exp(f)
+            assert_eq!(prefix(&(f.derivative() * &g), n - 1), g.derivative());
+        }
+    }
+}
+
+#[test]
+fn pow_random() {
+    type M = ConstMod<998244353>;
+    let mut rng = Xoshiro::seed_from_u64(3);
+    for _ in 0..50 {
+        let n = rng.range(0, 20);
+        let m = rng.range(0, 20);
+        let mut f = random_poly(&mut rng, m);
+        for i in 0..rng.range(0, 4).min(f.len()) {
+            f[i] = ModInt::ZERO;
+        }
+        let k = rng.range(0, 6);
+        let mut naive = Poly::<M>::from(vec![1]);
+        for _ in 0..k {
+            naive = prefix(&(naive * &f), n);
+        }
+        assert_eq!(f.pow(k, n), prefix(&naive, n), "f={f} k={k} n={n}");
+    }
+    let x = Poly::<M>::from(vec![0, 1]);
+    assert_eq!(x.pow(1_000_000_000_000_000_000, 10), Poly::zero());
+    assert_eq!(Poly::<M>::zero().pow(0, 3), Poly::from(vec![1]));
+}
+
+#[test]
+fn sqrt_random() {
+    let mut rng = Xoshiro::seed_from_u64(4);
+    for _ in 0..50 {
+        let n = rng.range(0, 40);
+        let m = rng.range(0, 40);
+        let mut g = random_poly(&mut rng, m);
+        for i in 0..rng.range(0, 4).min(g.len()) {
+            g[i] = ModInt::ZERO;
+        }
+        let f = prefix(&(&g * &g), n);
+        let h = f.sqrt(n).unwrap();
+        assert_eq!(prefix(&(&h * &h), n), f);
+    }
+    type M = ConstMod<998244353>;
+    assert!(Poly::<M>::from(vec![0, 1]).sqrt(3).is_none());
+    assert!(Poly::<M>::from(vec![3]).sqrt(3).is_none());
+    assert_eq!(Poly::<M>::from(vec![0, 1]).sqrt(1), Some(Poly::zero()));
+}
+
+#[test]
+fn taylor_shift_random() {
+    type M = ConstMod<998244353>;
+    let mut rng = Xoshiro::seed_from_u64(5);
+    for n in 0..40 {
+        let f = random_poly(&mut rng, n);
+        let c: ModInt<M> = ModInt::from(rng.range(0u32, 998244353));
+        let g = f.taylor_shift(c);
+        for _ in 0..5 {
+            let x: ModInt<M> = ModInt::from(rng.range(0u32, 998244353));
+            assert_eq!(g.evaluate(x), f.evaluate(x + c));
+        }
+    }
+}
+
+#[test]
+fn mint_sqrt() {
+    type M = ConstMod<998244353>;
+    for x in 0..1000u32 {
+        let x = ModInt::<M>::new(x);
+        if let Some(y) = x.sqrt() {
+            assert_eq!(y * y, x);
+            assert!(y.get() <= (-y).get() || y.get() == 0);
+        } else {
+            assert_ne!(x.pow(998244352 / 2).get(), 1);
+        }
+    }
+}
+
+#[test]
+fn evaluate_many_interpolate() {
+    type M = ConstMod<998244353>;
+    let mut rng = Xoshiro::seed_from_u64(6);
+    for n in [0, 1, 2, 3, 5, 17, 64, 100] {
+        let m = rng.range(0, 2 * n + 2);
+        let f = random_poly(&mut rng, m);
+        let mut xs: Vec<ModInt<M>> = vec![];
+        while xs.len() < n {
+            let x = ModInt::from(rng.range(0u32, 998244353));
+            if !xs.contains(&x) {
+                xs.push(x);
+            }
+        }
+        let tree = SubproductTree::new(&xs);
+        let naive: Vec<_> = xs.iter().map(|&x| f.evaluate(x)).collect();
+        assert_eq!(f.evaluate_many(&xs), naive);
+        assert_eq!(tree.evaluate(&f), naive);
+        let g = tree.interpolate(&naive);
+        assert!(n == 0 || g.deg() == !0 || g.deg() < n);
+        assert_eq!(tree.evaluate(&g), naive);
+        if f.deg() == !0 || f.deg() < n {
+            assert_eq!(Poly::interpolate(&xs, &naive), f);
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use crate::modint2::Pow;
+use crate::montgomery::*;
+use crate::random::*;
+
+fn check<M: MontgomeryModulo>(rng: &mut Xoshiro) {
+    let m = MontgomeryModInt::<M>::modulo();
+    for _ in 0..1000 {
+        let x = rng.range(0, m);
+        let y = rng.range(0, m);
+        let a = MontgomeryModInt::<M>::new(x);
+        let b = MontgomeryModInt::<M>::new(y);
+        assert_eq!(a.get(), x);
+        assert_eq!((a + b).get(), ((x as u128 + y as u128) % m as u128) as u64);
+        assert_eq!(
+            (a - b).get(),
+            ((x as u128 + m as u128 - y as u128) % m as u128) as u64
+        );
+        assert_eq!((a * b).get(), (x as u128 * y as u128 % m as u128) as u64);
+        assert_eq!((-a + a).get(), 0);
+        if x != 0 {
+            assert_eq!((a * a.inv()).get(), 1);
+            assert_eq!((b / a * a), b);
+        }
+    }
+    let a = MontgomeryModInt::<M>::from(-1i32);
+    assert_eq!(a.get(), m - 1);
+    assert_eq!(a.pow(2u32).get(), 1);
+    let v: Vec<MontgomeryModInt<M>> = (1..=10u32).map(MontgomeryModInt::from).collect();
+    assert_eq!(v.iter().sum::<MontgomeryModInt<M>>().get(), 55 % m);
+    assert_eq!(v.iter().product::<MontgomeryModInt<M>>().get(), 3628800 % m);
+}
+
+#[test]
+fn const_moduli() {
+    let mut rng = Xoshiro::seed_from_u64(1);
+    check::<ConstMont32<998244353>>(&mut rng);
+    check::<ConstMont32<1_000_000_007>>(&mut rng);
+    check::<ConstMont32<2147483647>>(&mut rng);
+    check::<ConstMont64<998244353>>(&mut rng);
+    check::<ConstMont64<4611686018427387847>>(&mut rng);
+    check::<ConstMont64<9223372036854775783>>(&mut rng);
+}
+
+#[test]
+fn var_moduli() {
+    let mut rng = Xoshiro::seed_from_u64(2);
+    for m in [3, 5, 1_000_000_007, 2147483647] {
+        set_var_mont32(m);
+        check::<VarMont32>(&mut rng);
+    }
+    for m in [3, 1_000_000_007, (1 << 61) - 1, 9223372036854775783] {
+        set_var_mont64(m);
+        check::<VarMont64>(&mut rng);
+    }
+}
+
+#[test]
+fn fermat() {
+    type Mint = Mont64<{ (1 << 61) - 1 }>;
+    let a = Mint::new(123456789);
+    assert_eq!(a.pow((1u64 << 61) - 3), a.inv());
+    assert_eq!(a.pow((1u64 << 61) - 1), a);
+}
@@ -0,0 +1,162 @@
+use crate::dsu::Dsu;
+use crate::mst::*;
+use crate::random::*;
+
+fn random_edges(rng: &mut Xoshiro, n: usize, m: usize, hi: u32) -> Vec<(usize, usize, u32)> {
+    (0..m)
+        .map(|_| (rng.range(0, n), rng.range(0, n), rng.range_inclusive(0, hi)))
+        .collect()
+}
+
+/// All spanning forests as `(weight, mask)`.
+fn spanning_forests(n: usize, edges: &[(usize, usize, u32)]) -> Vec<(u32, u32)> {
+    let mut dsu = Dsu::new(n);
+    for &(u, v, _) in edges {
+        dsu.unite(u, v);
+    }
+    let size = n - dsu.components().count();
+    (0..1u32 << edges.len())
+        .filter(|mask| mask.count_ones() as usize == size)
+        .filter_map(|mask| {
+            let mut dsu = Dsu::new(n);
+            let mut w = 0;
+            for (i, &(u, v, c)) in edges.iter().enumerate() {
+                if mask >> i & 1 == 1 {
+                    if !dsu.unite(u, v).is_modified() {
+                        return None;
+                    }
+                    w += c;
+                }
+            }
+            Some((w, mask))
+        })
+        .collect()
+}
+
+#[test]
+fn spanning_forest() {
+    let mut rng = Xoshiro::seed_from_u64(1);
+    for _ in 0..300 {
+        let n = rng.range_inclusive(1, 6);
+        let m = rng.range(0, 11);
+        let edges = random_edges(&mut rng, n, m, 5);
+        let forests = spanning_forests(n, &edges);
+        let best = forests.iter().map(|&(w, _)| w).min().unwrap();
+        let (total, chosen) = kruskal(n, &edges);
+        assert_eq!(total, best);
+        let mask = chosen.iter().map(|&i| 1 << i).sum::<u32>();
+        assert!(forests.contains(&(best, mask)));
+        for (total_, chosen_) in [prim(n, &edges), boruvka(n, &edges)] {
+            assert_eq!(total_, best);
+            let mask = chosen_.iter().map(|&i| 1 << i).sum::<u32>();
+            assert!(forests.contains(&(best, mask)));
+        }
+
+        let second = forests
+            .iter()
+            .filter(|&&(_, m)| m != mask)
+            .map(|&(w, _)| w)
+            .min();
+        assert_eq!(second_best_mst(n, &edges), second);
+
+        let in_some = in_some_mst(n, &edges);
+        for (i, &b) in in_some.iter().enumerate() {
+            let naive = forests.iter().any(|&(w, m)| w == best && m >> i & 1 == 1);
+            assert_eq!(b, naive);
+        }
+    }
+}
+
+fn naive_arborescence(n: usize, root: usize, edges: &[(usize, usize, u32)]) -> Option<u32> {
+    fn rec(
+        v: usize,
+        n: usize,
+        root: usize,
+        edges: &[(usize, usize, u32)],
+        par: &mut Vec<usize>,
+        best: &mut Option<u32>,
+    ) {
+        if v == n {
+            let reaches_root = (0..n).all(|mut u| {
+                for _ in 0..n {
+                    if u == root {
+                        break;
+                    }
+                    u = edges[par[u]].0;
+                }
+                u == root
+            });
+            if reaches_root {
+                let w = (0..n).filter(|&u| u != root).map(|u| edges[par[u]].2).sum();
+                *best = Some(best.map_or(w, |b: u32| b.min(w)));
+            }
+            return;
+        }
+        if v == root {
+            return rec(v + 1, n, root, edges, par, best);
+        }
+        for (i, e) in edges.iter().enumerate() {
+            if e.1 == v {
+                par[v] = i;
+                rec(v + 1, n, root, edges, par, best);
+            }
+        }
+    }
+    let mut best = None;
+    rec(0, n, root, edges, &mut vec![!0; n], &mut best);
+    best
+}
+
+#[test]
+fn arborescence() {
+    let mut rng = Xoshiro::seed_from_u64(2);
+    let mut found = 0;
+    for _ in 0..500 {
+        let n = rng.range_inclusive(1, 6);
+        let m = rng.range(0, 13);
+        let edges = random_edges(&mut rng, n, m, 10);
+        let root = rng.range(0, n);
+        let naive = naive_arborescence(n, root, &edges);
+        match min_arborescence(n, root, &edges) {
+            None => assert_eq!(naive, None),
+            Some((total, par)) => {
+                found += 1;
+                assert_eq!(Some(total), naive);
+                assert_eq!(par[root], !0);
+                let mut sum = 0;
+                for v in (0..n).filter(|&v| v != root) {
+                    let (_, to, w) = edges[par[v]];
+                    assert_eq!(to, v);
+                    sum += w;
+                    let mut u = v;
+                    for _ in 0..n {
+                        if u != root {
+                            u = edges[par[u]].0;
+                        }
+                    }
+                    assert_eq!(u, root);
+                }
+                assert_eq!(sum, total);
+            }
+        }
+    }
+    assert!(found > 100);
+}
+
+#[test]
+fn spanning_forest_large() {
+    let mut rng = Xoshiro::seed_from_u64(3);
+    for _ in 0..20 {
+        let n = rng.range_inclusive(1, 300);
+        let m = rng.range(0, 3 * n);
+        let edges = random_edges(&mut rng, n, m, 1000);
+        let (total, chosen) = kruskal(n, &edges);
+        assert_eq!(prim(n, &edges).0, total);
+        assert_eq!(boruvka(n, &edges).0, total);
+        let in_some = in_some_mst(n, &edges);
+        assert!(chosen.iter().all(|&i| in_some[i]));
+        if let Some(second) = second_best_mst(n, &edges) {
+            assert!(second >= total);
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use crate::algebra::*;
+use crate::persistent::*;
+use crate::random::*;
+
+#[test]
+fn seg_tree_versions() {
+    let mut rng = Xoshiro::seed_from_u64(1);
+    for n in 0..20 {
+        let a: Vec<i64> = (0..n).map(|_| rng.range(0, 100)).collect();
+        let mut st = PersistentSegTree::from(a.iter().map(|&x| Sum(x)).collect::<Vec<_>>());
+        let mut roots = vec![st.initial()];
+        let mut naive = vec![a];
+        for _ in 0..100 {
+            let v = rng.range(0, roots.len());
+            if n > 0 {
+                let i = rng.range(0, n);
+                let x = rng.range(0, 100);
+                roots.push(st.set(roots[v], i, Sum(x)));
+                let mut a = naive[v].clone();
+                a[i] = x;
+                naive.push(a);
+            }
+            let v = rng.range(0, roots.len());
+            let l = rng.range_inclusive(0, n);
+            let r = rng.range_inclusive(0, n);
+            let (l, r) = (l.min(r), l.max(r));
+            assert_eq!(st.sum(roots[v], l, r).0, naive[v][l..r].iter().sum::<i64>());
+            let x = rng.range(0, 300);
+            let naive_r = (l..=n)
+                .rev()
+                .find(|&r| naive[v][l..r].iter().sum::<i64>() <= x)
+                .unwrap();
+            assert_eq!(st.max_right(roots[v], l, |s| s.0 <= x), naive_r);
+            if n > 0 {
+                let i = rng.range(0, n);
+                assert_eq!(st.get(roots[v], i).0, naive[v][i]);
+            }
+        }
+    }
+}
+
+#[test]
+fn range_kth_smallest() {
+    let mut rng = Xoshiro::seed_from_u64(2);
+    let n = 50;
+    let m = 20;
+    let a: Vec<usize> = (0..n).map(|_| rng.range(0, m)).collect();
+    let mut st = PersistentSegTree::<Sum<i64>>::new(m);
+    let mut roots = vec![st.initial()];
+    for &x in &a {
+        let root = st.update(*roots.last().unwrap(), x, |c| Sum(c.0 + 1));
+        roots.push(root);
+    }
+    for l in 0..n {
+        for r in l + 1..=n {
+            let mut sorted = a[l..r].to_vec();
+            sorted.sort();
+            for (k, &x) in sorted.iter().enumerate() {
+                let kth = st.max_right_diff(roots[l], roots[r], 0, |c| c.0 <= k as i64);
+                assert_eq!(kth, x);
+            }
+        }
+    }
+}
+
+#[test]
+fn array_versions() {
+    let mut rng = Xoshiro::seed_from_u64(3);
+    for n in 0..20 {
+        let a: Vec<u32> = (0..n).map(|_| rng.range(0, 100)).collect();
+        let mut pa = PersistentArray::from(a.clone());
+        assert_eq!(pa.num_nodes(), n);
+        let mut roots = vec![pa.initial()];
+        let mut naive = vec![a];
+        for _ in 0..100 {
+            if n == 0 {
+                break;
+            }
+            let v = rng.range(0, roots.len());
+            let i = rng.range(0, n);
+            let x = rng.range(0, 100);
+            roots.push(pa.set(roots[v], i, x));
+            let mut a = naive[v].clone();
+            a[i] = x;
+            naive.push(a);
+        }
+        for (&root, a) in roots.iter().zip(&naive) {
+            assert_eq!(&pa.to_vec(root), a);
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use crate::int::gcd;
+use crate::primes::*;
+
+#[test]
+fn sieve_primes() {
+    for n in 0..200 {
+        let sieve = LinearSieve::new(n);
+        let ps: Vec<usize> = sieve.primes().iter().map(|&p| p as usize).collect();
+        assert_eq!(ps, primes(n));
+        for x in 0..=n {
+            assert_eq!(sieve.is_prime(x), ps.contains(&x));
+        }
+    }
+    let sieve = LinearSieve::new(1_000_000);
+    assert_eq!(sieve.primes().len(), 78498);
+    assert_eq!(sieve.primes().len(), primes(1_000_000).len());
+}
+
+#[test]
+fn sieve_factorize() {
+    let sieve = LinearSieve::new(10000);
+    for x in 1..=10000 {
+        let f = sieve.factorize(x);
+        assert_eq!(f.iter().map(|&(p, e)| p.pow(e)).product::<usize>(), x);
+        assert!(f.windows(2).all(|w| w[0].0 < w[1].0));
+        assert!(f.iter().all(|&(p, _)| sieve.is_prime(p)));
+        if x >= 2 {
+            assert_eq!(sieve.spf(x), f[0].0);
+        }
+    }
+}
+
+#[test]
+fn multiplicative_tables() {
+    let n = 1000;
+    let sieve = LinearSieve::new(n);
+    let mobius = sieve.mobius();
+    let phi = sieve.euler_phi();
+    let d = sieve.divisor_count();
+    let sigma = sieve.divisor_sum();
+    for x in 1..=n {
+        let divs: Vec<usize> = (1..=x).filter(|k| x % k == 0).collect();
+        assert_eq!(d[x] as usize, divs.len());
+        assert_eq!(sigma[x] as usize, divs.iter().sum::<usize>());
+        let coprime = (1..=x).filter(|&k| gcd(k, x) == 1).count();
+        assert_eq!(phi[x] as usize, coprime);
+        let squarefree = sieve.factorize(x).iter().all(|&(_, e)| e == 1);
+        let expected = if squarefree {
+            (-1i32).pow(sieve.factorize(x).len() as u32)
+        } else {
+            0
+        };
+        assert_eq!(mobius[x], expected);
+        // sum_{d | x} mu(d) = [x == 1]
+        let s: i32 = divs.iter().map(|&k| mobius[k]).sum();
+        assert_eq!(s, (x == 1) as i32);
+    }
+    assert_eq!(LinearSieve::new(0).mobius(), vec![0]);
+}
+
+#[test]
+fn lucy_prime_count() {
+    let n = 100000;
+    let ps = primes(n);
+    for x in (0..200).chain([9999, 10000, 65536, 99991, 100000]) {
+        let below: Vec<usize> = ps.iter().copied().take_while(|&p| p <= x).collect();
+        assert_eq!(prime_count(x as u64), below.len() as u64, "{}", x);
+        assert_eq!(prime_sum(x as u64), below.iter().sum::<usize>() as u128);
+    }
+    assert_eq!(prime_count(1_000_000_000), 50847534);
+    assert_eq!(prime_count(10_000_000_000), 455052511);
+    assert_eq!(prime_sum(2_000_000), 142913828922);
+}
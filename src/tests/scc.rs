@@ -0,0 +1,55 @@
+use crate::random::*;
+use crate::scc::*;
+
+fn reachable(g: &[Vec<usize>]) -> Vec<Vec<bool>> {
+    (0..g.len())
+        .map(|s| {
+            let mut r = vec![false; g.len()];
+            let mut stk = vec![s];
+            r[s] = true;
+            while let Some(u) = stk.pop() {
+                for &v in &g[u] {
+                    if !r[v] {
+                        r[v] = true;
+                        stk.push(v);
+                    }
+                }
+            }
+            r
+        })
+        .collect()
+}
+
+#[test]
+fn condense_random() {
+    let mut rng = Xoshiro::seed_from_u64(1);
+    for _ in 0..200 {
+        let n = rng.range_inclusive(1, 12);
+        let m = rng.range(0, 3 * n);
+        let mut g = vec![vec![]; n];
+        for _ in 0..m {
+            g[rng.range(0, n)].push(rng.range(0, n));
+        }
+        let r = reachable(&g);
+        let (comp, dag) = condense(n, |u| g[u].iter().copied());
+        for u in 0..n {
+            for v in 0..n {
+                assert_eq!(comp[u] == comp[v], r[u][v] && r[v][u]);
+            }
+        }
+        let mut expected = vec![vec![]; dag.len()];
+        for u in 0..n {
+            for &v in &g[u] {
+                if comp[u] != comp[v] {
+                    expected[comp[u]].push(comp[v]);
+                }
+            }
+        }
+        for (c, succ) in expected.iter_mut().enumerate() {
+            succ.sort();
+            succ.dedup();
+            assert_eq!(dag[c], *succ);
+            assert!(succ.iter().all(|&d| c < d));
+        }
+    }
+}
@@ -57,3 +57,29 @@ impl Monoid for Affine {
         affine(self.a * other.a, self.a * other.b + self.b)
     }
 }
+
+#[test]
+fn max_right_min_left() {
+    use crate::algebra::Sum;
+    let mut rng = Xoshiro::seed_from_u64(1);
+    for n in 0..40 {
+        let mut a: Vec<i64> = (0..n).map(|_| rng.range(0, 10)).collect();
+        let mut st: SegTree<Sum<i64>> = a.iter().map(|&x| Sum(x)).collect();
+        for _ in 0..200 {
+            if n > 0 && rng.range(0, 3) == 0 {
+                let i = rng.range(0, n);
+                a[i] = rng.range(0, 10);
+                st.set(i, Sum(a[i]));
+            }
+            let i = rng.range_inclusive(0, n);
+            let x = rng.range(0, 50);
+            let naive_r = (i..=n)
+                .rev()
+                .find(|&r| a[i..r].iter().sum::<i64>() <= x)
+                .unwrap();
+            assert_eq!(st.max_right(i, |s| s.0 <= x), naive_r);
+            let naive_l = (0..=i).find(|&l| a[l..i].iter().sum::<i64>() <= x).unwrap();
+            assert_eq!(st.min_left(i, |s| s.0 <= x), naive_l);
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use crate::random::*;
+use crate::shortest_path::*;
+
+fn random_graph(rng: &mut Xoshiro, n: usize, m: usize, lo: i64, hi: i64) -> Vec<Vec<(usize, i64)>> {
+    let mut g = vec![vec![]; n];
+    for _ in 0..m {
+        let u = rng.range(0, n);
+        g[u].push((rng.range(0, n), rng.range_inclusive(lo, hi)));
+    }
+    g
+}
+
+/// Bellman-Ford on the edge list; `None` on a negative cycle anywhere.
+fn naive_all_pairs(g: &[Vec<(usize, i64)>]) -> Option<Vec<Vec<i64>>> {
+    let n = g.len();
+    let inf = i64::MAX;
+    let mut d = vec![vec![inf; n]; n];
+    for (s, ds) in d.iter_mut().enumerate() {
+        ds[s] = 0;
+        for _ in 0..2 * n + 1 {
+            for (u, es) in g.iter().enumerate() {
+                for &(v, w) in es {
+                    if ds[u] != inf && ds[u] + w < ds[v] {
+                        ds[v] = ds[u] + w;
+                    }
+                }
+            }
+        }
+    }
+    let stable = (0..n).all(|s| {
+        (0..n).all(|u| {
+            g[u].iter()
+                .all(|&(v, w)| d[s][u] == inf || d[s][u] + w >= d[s][v])
+        })
+    });
+    stable.then_some(d)
+}
+
+fn check_paths(g: &[Vec<(usize, i64)>], s: usize, sp: &ShortestPaths<i64>, dist: &[i64]) {
+    assert_eq!(sp.dist, dist);
+    for (t, &dt) in dist.iter().enumerate() {
+        match sp.path(t) {
+            None => assert_eq!(dt, i64::MAX),
+            Some(path) => {
+                assert_eq!(path[0], s);
+                assert_eq!(*path.last().unwrap(), t);
+                let len: i64 = path
+                    .windows(2)
+                    .map(|e| {
+                        g[e[0]]
+                            .iter()
+                            .filter(|&&(v, _)| v == e[1])
+                            .map(|&(_, w)| w)
+                            .min()
+                            .unwrap()
+                    })
+                    .sum();
+                assert_eq!(len, dt);
+            }
+        }
+    }
+}
+
+#[test]
+fn nonnegative_weights() {
+    let mut rng = Xoshiro::seed_from_u64(1);
+    for _ in 0..200 {
+        let n = rng.range_inclusive(1, 10);
+        let m = rng.range(0, 4 * n);
+        let g = random_graph(&mut rng, n, m, 0, 20);
+        let d = naive_all_pairs(&g).unwrap();
+        let gu: Vec<Vec<(usize, u64)>> = g
+            .iter()
+            .map(|a| a.iter().map(|&(v, w)| (v, w as u64)).collect())
+            .collect();
+        for (s, ds) in d.iter().enumerate() {
+            check_paths(&g, s, &dijkstra(&g[..], s), ds);
+            check_paths(&g, s, &bellman_ford(&g[..], s).unwrap(), ds);
+            check_paths(&g, s, &spfa(&g[..], s).unwrap(), ds);
+            let radix = dijkstra_radix(&gu[..], s);
+            let expected: Vec<u64> = ds
+                .iter()
+                .map(|&x| if x == i64::MAX { u64::MAX } else { x as u64 })
+                .collect();
+            assert_eq!(radix.dist, expected);
+            for (t, &dt) in ds.iter().enumerate() {
+                assert_eq!(radix.path(t).is_some(), dt != i64::MAX);
+            }
+        }
+        assert_eq!(floyd_warshall(&g[..]).unwrap(), d);
+        let all = johnson(&g[..]).unwrap();
+        for s in 0..n {
+            check_paths(&g, s, &all[s], &d[s]);
+        }
+    }
+}
+
+#[test]
+fn zero_one_weights() {
+    let mut rng = Xoshiro::seed_from_u64(2);
+    for _ in 0..200 {
+        let n = rng.range_inclusive(1, 10);
+        let m = rng.range(0, 4 * n);
+        let g: Vec<Vec<(usize, i64)>> = random_graph(&mut rng, n, m, 0, 1)
+            .into_iter()
+            .map(|a| a.into_iter().map(|(v, w)| (v, 5 * w)).collect())
+            .collect();
+        let d = naive_all_pairs(&g).unwrap();
+        for (s, ds) in d.iter().enumerate() {
+            check_paths(&g, s, &bfs01(&g[..], s), ds);
+        }
+    }
+}
+
+#[test]
+fn negative_weights() {
+    let mut rng = Xoshiro::seed_from_u64(3);
+    let mut cycles = 0;
+    for _ in 0..500 {
+        let n = rng.range_inclusive(1, 8);
+        let m = rng.range(0, 3 * n);
+        let g = random_graph(&mut rng, n, m, -5, 20);
+        let naive = naive_all_pairs(&g);
+        let fw = floyd_warshall(&g[..]);
+        let johnson = johnson(&g[..]);
+        match naive {
+            Some(d) => {
+                assert_eq!(fw.unwrap(), d);
+                let all = johnson.unwrap();
+                for s in 0..n {
+                    check_paths(&g, s, &bellman_ford(&g[..], s).unwrap(), &d[s]);
+                    check_paths(&g, s, &spfa(&g[..], s).unwrap(), &d[s]);
+                    check_paths(&g, s, &all[s], &d[s]);
+                }
+            }
+            None => {
+                cycles += 1;
+                assert!(fw.is_none());
+                assert!(johnson.is_none());
+                // Only sources that reach the cycle see it.
+                let reach_cycle = |s: usize| {
+                    let mut d = vec![i64::MAX; n];
+                    d[s] = 0;
+                    for _ in 0..n {
+                        for u in 0..n {
+                            for &(v, w) in &g[u] {
+                                if d[u] != i64::MAX && d[u] + w < d[v] {
+                                    d[v] = d[u] + w;
+                                }
+                            }
+                        }
+                    }
+                    (0..n).any(|u| {
+                        g[u].iter()
+                            .any(|&(v, w)| d[u] != i64::MAX && d[u] + w < d[v])
+                    })
+                };
+                for s in 0..n {
+                    assert_eq!(bellman_ford(&g[..], s).is_none(), reach_cycle(s));
+                    assert_eq!(spfa(&g[..], s).is_none(), reach_cycle(s));
+                }
+            }
+        }
+    }
+    assert!(cycles > 10);
+}
+
+#[test]
+fn floyd_warshall_large_negative_cycle() {
+    let n = 100;
+    let g: Vec<Vec<(usize, i64)>> = (0..n)
+        .map(|u| {
+            (0..n)
+                .map(|v| (v, if v == (u + 1) % n { -1 } else { 1 }))
+                .collect()
+        })
+        .collect();
+    assert!(floyd_warshall(&g[..]).is_none());
+    assert!(johnson(&g[..]).is_none());
+}
@@ -0,0 +1,77 @@
+use crate::random::*;
+use crate::slope_trick::*;
+
+const W: i64 = 300;
+
+#[test]
+fn random_ops() {
+    let mut rng = Xoshiro::seed_from_u64(1);
+    for _ in 0..100 {
+        let mut st = SlopeTrick::<i64>::new();
+        let mut naive = vec![0i64; 2 * W as usize + 1];
+        for _ in 0..30 {
+            let a = rng.range_inclusive(-20, 20);
+            match rng.range(0, 7) {
+                0 => {
+                    st.add_x_minus_a(a);
+                    for (i, y) in naive.iter_mut().enumerate() {
+                        *y += (i as i64 - W - a).max(0);
+                    }
+                }
+                1 => {
+                    st.add_a_minus_x(a);
+                    for (i, y) in naive.iter_mut().enumerate() {
+                        *y += (a - (i as i64 - W)).max(0);
+                    }
+                }
+                2 => {
+                    st.add_abs(a);
+                    for (i, y) in naive.iter_mut().enumerate() {
+                        *y += (i as i64 - W - a).abs();
+                    }
+                }
+                3 => {
+                    st.prefix_min();
+                    for i in 1..naive.len() {
+                        naive[i] = naive[i].min(naive[i - 1]);
+                    }
+                }
+                4 => {
+                    st.suffix_min();
+                    for i in (0..naive.len() - 1).rev() {
+                        naive[i] = naive[i].min(naive[i + 1]);
+                    }
+                }
+                5 => {
+                    let b = a + rng.range_inclusive(0, 3);
+                    st.sliding_window_min(a, b);
+                    let orig = naive.clone();
+                    for (i, y) in naive.iter_mut().enumerate() {
+                        let lo = (i as i64 - b).max(0);
+                        let hi = (i as i64 - a).min(2 * W);
+                        *y = (lo..=hi)
+                            .map(|j| orig[j as usize])
+                            .min()
+                            .unwrap_or(i64::MAX / 2);
+                    }
+                }
+                _ => {
+                    st.add_const(a);
+                    for y in &mut naive {
+                        *y += a;
+                    }
+                }
+            }
+            let mid = &naive[(W - 100) as usize..=(W + 100) as usize];
+            let min = *mid.iter().min().unwrap();
+            assert_eq!(st.min(), min);
+            let (l, r) = st.argmin();
+            for x in -100..=100 {
+                let y = naive[(x + W) as usize];
+                assert_eq!(st.eval(x), y);
+                let inside = l.unwrap_or(x) <= x && x <= r.unwrap_or(x);
+                assert_eq!(y == min, inside, "x={x}");
+            }
+        }
+    }
+}
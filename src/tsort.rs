@@ -1,32 +1,31 @@
-use crate::graph::*;
+use crate::graph2::IndexedGraph;
 
-pub fn tsort<'a, G: Graph<'a>>(g: &'a G) -> Option<Vec<usize>> {
+/// Topological order of the nodes, or `None` if the graph has a cycle.
+pub fn tsort<G: IndexedGraph + ?Sized>(g: &G) -> Option<Vec<usize>> {
     TSort::new(g).run()
 }
 
-struct TSort<'a, G> {
+struct TSort<'a, G: ?Sized> {
     g: &'a G,
     res: Vec<usize>,
     state: Vec<u8>,
 }
 
-impl<'a, G: Graph<'a>> TSort<'a, G> {
+impl<'a, G: IndexedGraph + ?Sized> TSort<'a, G> {
     fn new(g: &'a G) -> Self {
-        let n = g.num_verts();
+        let n = g.num_nodes();
         Self {
             g,
             res: Vec::with_capacity(n),
-            state: vec![0; (n + 3) / 4],
+            state: vec![0; n.div_ceil(4)],
         }
     }
 
     fn run(mut self) -> Option<Vec<usize>> {
-        let n = self.g.num_verts();
-        for s in self.g.verts().rev() {
-            if self.state(s) == 0 {
-                if !self.dfs(s) {
-                    return None;
-                }
+        let n = self.g.num_nodes();
+        for s in (0..n).rev() {
+            if self.state(s) == 0 && !self.dfs(s) {
+                return None;
             }
         }
         debug_assert_eq!(self.res.len(), n);
@@ -37,13 +36,10 @@ impl<'a, G: Graph<'a>> TSort<'a, G> {
     fn dfs(&mut self, u: usize) -> bool {
         debug_assert_eq!(self.state(u), 0);
         self.set_state(u, 1);
-        for v in self.g.adj(u) {
+        let g = self.g;
+        for v in g.neighbors(u) {
             match self.state(v) {
-                0 => {
-                    if !self.dfs(v) {
-                        return false;
-                    }
-                }
+                0 if !self.dfs(v) => return false,
                 1 => return false,
                 _ => {}
             }